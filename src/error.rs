@@ -0,0 +1,63 @@
+use std::{error::Error, fmt, io};
+
+use sqlparser::parser::ParserError;
+
+/// Everything that can go wrong while nit-picking.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AntError {
+    /// The input isn't SQL we (or rather, `sqlparser`) can read.
+    Parser(ParserError),
+    /// The input isn't SQL the chosen dialect can read, but it is MySQL, e.g.
+    /// its backquoted identifiers; the likeliest fix is
+    /// `--dialect mysql`.
+    MySqlOnly(ParserError),
+    /// A [`Separator::Custom`](crate::Separator::Custom) without a newline,
+    /// which would collapse the output onto a single line.
+    InvalidSeparator(String),
+    /// A dialect name we don't recognise.
+    UnknownDialect(String),
+    /// Reading or writing a file failed, e.g. in
+    /// [`AntFarmer::format_file_in_place`](crate::AntFarmer::format_file_in_place).
+    Io(io::Error),
+}
+
+impl fmt::Display for AntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AntError::Parser(err) => write!(f, "{}", err),
+            AntError::MySqlOnly(err) => write!(
+                f,
+                "{} (hint: this parses as MySQL; try --dialect mysql)",
+                err
+            ),
+            AntError::InvalidSeparator(separator) => {
+                write!(f, "separator {:?} must contain a newline", separator)
+            }
+            AntError::UnknownDialect(name) => write!(f, "unknown dialect: {}", name),
+            AntError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for AntError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AntError::Parser(err) | AntError::MySqlOnly(err) => Some(err),
+            AntError::Io(err) => Some(err),
+            AntError::InvalidSeparator(_) | AntError::UnknownDialect(_) => None,
+        }
+    }
+}
+
+impl From<ParserError> for AntError {
+    fn from(err: ParserError) -> Self {
+        AntError::Parser(err)
+    }
+}
+
+impl From<io::Error> for AntError {
+    fn from(err: io::Error) -> Self {
+        AntError::Io(err)
+    }
+}
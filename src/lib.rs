@@ -1,11 +1,322 @@
+use std::{
+    borrow::{Borrow, Cow},
+    collections::HashSet,
+    fmt::Display,
+    fs, iter, mem,
+    path::Path,
+};
+
 use sqlparser::{
-    ast::{ColumnDef, ColumnOption, Statement, TableConstraint},
-    dialect::Dialect,
-    parser::{Parser, ParserError},
+    ast::{
+        AlterTable, AlterTableOperation, CastKind, CheckConstraint, ColumnDef, ColumnOption,
+        ColumnOptionDef, CreateTable, CreateTableOptions, DataType, ExcludeConstraint, Expr,
+        ForeignKeyConstraint, Ident, IndexOption, IndexType, Insert, MySQLColumnPosition,
+        ObjectName, PrimaryKeyConstraint, ReferentialAction, SetExpr, SqlOption, Statement,
+        TableConstraint, UniqueConstraint, Value,
+    },
+    dialect::{
+        Dialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+    },
+    keywords::Keyword,
+    parser::{Parser, ParserError, ParserOptions},
+    tokenizer::{Location, Token, TokenWithSpan, Tokenizer, Whitespace},
 };
 
+mod error;
+mod options;
+mod warning;
+
+pub use error::AntError;
+pub use options::{FormatOptions, KeywordCase, Separator};
+pub use warning::Warning;
+
+/// What we glean from a statement's tokens that the parser doesn't keep.
+#[derive(Default)]
+struct Source {
+    /// The unquoted words of the statement, as written.
+    words: Vec<String>,
+    /// The comments following each of a `CREATE TABLE`'s columns, in order.
+    column_comments: Vec<Option<String>>,
+    /// Likewise for its constraints.
+    constraint_comments: Vec<Option<String>>,
+    /// The statement as written, up to and including its `;`, for those
+    /// statements passed through as they are.
+    text: String,
+    /// Whether the statement ends the input without a `;`, in which case it
+    /// gets none in the output either.
+    unterminated: bool,
+    /// Whether the statement has any of MySQL's version-conditional comments,
+    /// e.g. `/*!40101 SET … */`, whose version gates formatting would lose;
+    /// such statements are passed through as they are.
+    conditional: bool,
+}
+
+impl Source {
+    /// What there is to glean from a statement's `tokens`.
+    ///
+    /// Comments within a `CREATE TABLE`'s parentheses belong to whichever
+    /// column or constraint precedes them, e.g. `id INT, -- the key`.
+    fn from_tokens(tokens: &[TokenWithSpan]) -> Self {
+        let mut source = Source::default();
+        // Each top-level item in parentheses: whether it's a constraint, and
+        // its comments.
+        let mut items: Vec<(bool, Vec<String>)> = vec![];
+        let mut pending = vec![];
+        let mut depth = 0;
+        let mut in_item = false;
+        for TokenWithSpan { token, .. } in tokens {
+            let comment = match token {
+                Token::Whitespace(Whitespace::SingleLineComment { comment, prefix }) => {
+                    Some(format!("{}{}", prefix, comment.trim_end()))
+                }
+                Token::Whitespace(Whitespace::MultiLineComment(comment)) => {
+                    Some(format!("/*{}*/", comment))
+                }
+                _ => None,
+            };
+            if let Some(comment) = comment {
+                if depth > 0 {
+                    match items.last_mut() {
+                        Some((_, comments)) => comments.push(comment),
+                        None => pending.push(comment),
+                    }
+                }
+                continue;
+            }
+
+            if depth == 1 && !in_item && !matches!(token, Token::Whitespace(_) | Token::RParen) {
+                let mut comments = vec![];
+                comments.append(&mut pending);
+                items.push((Source::is_constraint(token), comments));
+                in_item = true;
+            }
+
+            match token {
+                Token::Word(word) if word.quote_style.is_none() => {
+                    source.words.push(word.value.clone())
+                }
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                Token::Comma if depth == 1 => in_item = false,
+                _ => {}
+            }
+        }
+        source.push_items(items);
+
+        source
+    }
+
+    /// Files each `(is_constraint, comments)` item's comments under columns or
+    /// constraints.
+    fn push_items(&mut self, items: impl IntoIterator<Item = (bool, Vec<String>)>) {
+        for (is_constraint, comments) in items {
+            let comment = (!comments.is_empty()).then(|| comments.join(" "));
+            if is_constraint {
+                self.constraint_comments.push(comment);
+            } else {
+                self.column_comments.push(comment);
+            }
+        }
+    }
+
+    /// Whether an item of a `CREATE TABLE` starting with `token` is a
+    /// constraint rather than a column.
+    fn is_constraint(token: &Token) -> bool {
+        const CONSTRAINTS: [&str; 10] = [
+            "CHECK",
+            "CONSTRAINT",
+            "EXCLUDE",
+            "FOREIGN",
+            "FULLTEXT",
+            "INDEX",
+            "KEY",
+            "PRIMARY",
+            "SPATIAL",
+            "UNIQUE",
+        ];
+
+        matches!(
+            token,
+            Token::Word(word) if word.quote_style.is_none()
+                && CONSTRAINTS.iter().any(|keyword| word.value.eq_ignore_ascii_case(keyword))
+        )
+    }
+}
+
+/// Everything a statement's components need to know while being rendered.
+struct Context<'a> {
+    dialect: &'a dyn Dialect,
+    options: &'a FormatOptions,
+    source: &'a Source,
+    /// The widths every `CREATE TABLE` shares, as per
+    /// [`FormatOptions::global_alignment`]; otherwise, each has its own.
+    widths: Option<&'a TableWidths>,
+    /// The statement's unquoted identifiers; see [`identifiers`].
+    identifiers: &'a HashSet<String>,
+}
+
+/// The widths of the segments of a `CREATE TABLE`'s columns and constraints.
+#[derive(Default)]
+struct TableWidths {
+    columns: Vec<usize>,
+    constraints: Vec<usize>,
+}
+
+impl Context<'_> {
+    /// Whether a foreign key's `ON DELETE`/`ON UPDATE` action should be
+    /// emitted, as per [`FormatOptions::explicit_referential_actions`].
+    fn emits_action(&self, action: &ReferentialAction) -> bool {
+        self.options.explicit_referential_actions
+            || !matches!(
+                action,
+                ReferentialAction::Restrict | ReferentialAction::NoAction
+            )
+    }
+
+    /// Cases a keyword (or run of keywords, e.g. `NOT NULL`) as per
+    /// [`FormatOptions::keyword_case`].
+    fn keyword(&self, keyword: &str) -> String {
+        self.cased(self.options.keyword_case, keyword)
+    }
+
+    fn cased(&self, case: KeywordCase, keyword: &str) -> String {
+        match case {
+            KeywordCase::Upper => keyword.to_uppercase(),
+            KeywordCase::Lower => keyword.to_lowercase(),
+            KeywordCase::Preserve => {
+                let wanted = keyword.split_whitespace().collect::<Vec<_>>();
+                if wanted.is_empty() {
+                    return keyword.to_string();
+                }
+
+                self.source
+                    .words
+                    .windows(wanted.len())
+                    .find(|words| {
+                        words
+                            .iter()
+                            .zip(wanted.iter())
+                            .all(|(word, wanted)| word.eq_ignore_ascii_case(wanted))
+                    })
+                    .map(|words| words.join(" "))
+                    .unwrap_or_else(|| keyword.to_string())
+            }
+        }
+    }
+
+    /// Renders a data type, casing its name as per
+    /// [`FormatOptions::data_type_case`]. Anything in parentheses or quotes,
+    /// e.g. `ENUM('a', 'b')`'s values, is left as it is.
+    fn data_type(&self, data_type: &DataType) -> String {
+        let data_type = data_type.to_string();
+        let Some(case) = self.options.data_type_case else {
+            return data_type;
+        };
+
+        let mut output = String::new();
+        let mut word = String::new();
+        let mut depth = 0;
+        let mut quote = None;
+        for c in data_type.chars() {
+            if depth == 0 && quote.is_none() && (c.is_alphanumeric() || c == '_') {
+                word.push(c);
+                continue;
+            }
+            if !word.is_empty() {
+                output += &self.cased(case, &word);
+                word.clear();
+            }
+
+            match (quote, c) {
+                (Some(q), _) if q == c => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => depth -= 1,
+                _ => {}
+            }
+            output.push(c);
+        }
+        if !word.is_empty() {
+            output += &self.cased(case, &word);
+        }
+
+        output
+    }
+
+    /// Renders a default, casing the target of any trailing `::` cast (e.g.
+    /// `'{}'::jsonb`) as per [`FormatOptions::data_type_case`], like the
+    /// column's own type.
+    fn default(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Cast {
+                kind: CastKind::DoubleColon,
+                expr,
+                data_type,
+                format: None,
+            } => format!("{}::{}", self.default(expr), self.data_type(data_type)),
+            _ => expr.to_string(),
+        }
+    }
+
+    /// The indent of the first column, matching however far the separator
+    /// pushes every other line.
+    fn indent(&self) -> String {
+        self.options
+            .separator
+            .as_str()
+            .rsplit('\n')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect()
+    }
+
+    /// Applies [`Context::keyword`] to every keyword in some SQL rendered by
+    /// `sqlparser`.
+    ///
+    /// `sqlparser` always renders keywords in uppercase and identifiers as they
+    /// were written, so only uppercase keywords are touched, and not those
+    /// which are the statement's identifiers, e.g. `TRUNCATE TABLE USER`.
+    fn recase(&self, sql: &str) -> String {
+        match Tokenizer::new(self.dialect, sql)
+            .with_unescape(false)
+            .tokenize()
+        {
+            Ok(tokens) => tokens
+                .iter()
+                .map(|token| match token {
+                    Token::Word(word)
+                        if word.quote_style.is_none()
+                            && word.keyword != Keyword::NoKeyword
+                            && word.value == word.value.to_uppercase()
+                            && !self.identifiers.contains(&word.value) =>
+                    {
+                        self.keyword(&word.value)
+                    }
+                    _ => token.to_string(),
+                })
+                .collect(),
+            Err(_) => sql.to_string(),
+        }
+    }
+}
+
 trait AlignedDisplay {
-    fn segments(&self) -> Vec<String>;
+    fn segments(&self, context: &Context) -> Vec<String>;
+}
+
+/// Renders a list of columns as a parenthesised, comma-separated list.
+fn parenthesised<T: Display>(columns: &[T]) -> String {
+    format!(
+        "({})",
+        columns
+            .iter()
+            .map(|column| column.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
 }
 
 /// Holds the components of a constraint definition about which we care for
@@ -13,31 +324,56 @@ trait AlignedDisplay {
 ///
 /// ```sql
 /// CREATE TABLE table_name (
-///   , CONSTRAINT NAME   FOREIGN KEY       (COLUMN)   REFERENCES TARGET_TABLE   (TARGET_COLUMN)
-///   , CONSTRAINT {name} {constraint_type} ({column}) REFERENCES {target_table} ({target_column})
+///   , CONSTRAINT NAME   PRIMARY KEY                    (COLUMN)   USING BTREE
+///   ,                   UNIQUE KEY        INDEX_NAME   (COLUMN)
+///   , CONSTRAINT NAME   FOREIGN KEY                    (COLUMN)                REFERENCES TARGET_TABLE   (TARGET_COLUMN)
+///   , CONSTRAINT {name} {constraint_type} {index_name} ({column}) {index_type} REFERENCES {target_table} ({target_column})
 /// )
 /// ;
 /// ```
 impl AlignedDisplay for TableConstraint {
-    fn segments(&self) -> Vec<String> {
-        match self {
-            TableConstraint::Unique {
+    fn segments(&self, context: &Context) -> Vec<String> {
+        let mut segments = match self {
+            TableConstraint::Unique(UniqueConstraint {
                 name,
+                index_name,
+                index_type_display,
                 columns,
-                is_primary,
-            } => {
+                index_type,
+                index_options,
+                ..
+            }) => {
                 vec![
-                    format!("CONSTRAINT {}", name.clone().unwrap().to_string()),
-                    if *is_primary {
-                        "PRIMARY KEY".to_string()
+                    constraint_name_segment(name, context),
+                    if index_type_display.is_none() {
+                        context.keyword("UNIQUE")
                     } else {
-                        "UNIQUE".to_string()
+                        context.keyword(&format!("UNIQUE {}", index_type_display))
                     },
-                    columns
-                        .iter()
-                        .map(|column| column.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", "),
+                    index_name_segment(index_name),
+                    parenthesised(columns),
+                    index_type_segment(index_type, index_options, context),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                ]
+            }
+            TableConstraint::PrimaryKey(PrimaryKeyConstraint {
+                name,
+                index_name,
+                columns,
+                index_type,
+                index_options,
+                ..
+            }) => {
+                vec![
+                    constraint_name_segment(name, context),
+                    context.keyword("PRIMARY KEY"),
+                    index_name_segment(index_name),
+                    parenthesised(columns),
+                    index_type_segment(index_type, index_options, context),
                     "".to_string(),
                     "".to_string(),
                     "".to_string(),
@@ -45,45 +381,68 @@ impl AlignedDisplay for TableConstraint {
                     "".to_string(),
                 ]
             }
-            TableConstraint::ForeignKey {
+            TableConstraint::ForeignKey(ForeignKeyConstraint {
                 name,
+                index_name,
                 columns,
                 foreign_table,
                 referred_columns,
                 on_delete,
                 on_update,
-            } => {
+                ..
+            }) => {
                 vec![
-                    format!("CONSTRAINT {}", name.clone().unwrap().to_string()),
-                    "FOREIGN KEY".to_string(),
-                    columns
-                        .iter()
-                        .map(|column| column.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", "),
-                    "REFERENCES".to_string(),
+                    constraint_name_segment(name, context),
+                    context.keyword("FOREIGN KEY"),
+                    index_name_segment(index_name),
+                    parenthesised(columns),
+                    "".to_string(),
+                    context.keyword("REFERENCES"),
                     foreign_table.to_string(),
-                    referred_columns
-                        .iter()
-                        .map(|column| column.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", "),
-                    if let Some(action) = on_delete {
-                        format!("ON DELETE {}", action)
+                    if referred_columns.is_empty() {
+                        "".to_string()
+                    } else {
+                        parenthesised(referred_columns)
+                    },
+                    if let Some(action) = on_delete.filter(|action| context.emits_action(action)) {
+                        context.keyword(&format!("ON DELETE {}", action))
                     } else {
                         "".to_string()
                     },
-                    if let Some(action) = on_update {
-                        format!("ON UPDATE {}", action)
+                    if let Some(action) = on_update.filter(|action| context.emits_action(action)) {
+                        context.keyword(&format!("ON UPDATE {}", action))
                     } else {
                         "".to_string()
                     },
                 ]
             }
-            TableConstraint::Check { name, expr } => {
+            TableConstraint::Check(CheckConstraint { name, expr, .. }) => {
+                vec![
+                    constraint_name_segment(name, context),
+                    format!("{} ({})", context.keyword("CHECK"), expr),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                ]
+            }
+            // Neither this nor the fallback below counts towards the widths;
+            // see `is_aligned`.
+            TableConstraint::Exclude(constraint) => {
+                // Everything after the name is kept together, as written.
+                let exclude = ExcludeConstraint {
+                    name: None,
+                    ..constraint.clone()
+                };
                 vec![
-                    format!("CONSTRAINT {}", name.clone().unwrap().to_string()),
-                    format!("CHECK ({})", expr),
+                    constraint_name_segment(&constraint.name, context),
+                    context.recase(&exclude.to_string()),
+                    "".to_string(),
+                    "".to_string(),
                     "".to_string(),
                     "".to_string(),
                     "".to_string(),
@@ -92,231 +451,2265 @@ impl AlignedDisplay for TableConstraint {
                     "".to_string(),
                 ]
             }
+            // E.g. MySQL's `INDEX`/`FULLTEXT`, which have no name of their own
+            // to line up; better unaligned than lost.
+            _ => vec![
+                "".to_string(),
+                context.recase(&self.to_string()),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ],
+        };
+
+        if let TableConstraint::Unique(UniqueConstraint { columns, .. })
+        | TableConstraint::PrimaryKey(PrimaryKeyConstraint { columns, .. }) = self
+        {
+            let width = context.indent().len()
+                + segments
+                    .iter()
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| segment.len() + 1)
+                    .sum::<usize>();
+
+            if columns.len() > 1
+                && context
+                    .options
+                    .max_width
+                    .is_some_and(|max_width| width > max_width)
+            {
+                segments[3] = wrapped(columns, context);
+            }
         }
+
+        segments
     }
 }
 
+/// Whether a constraint's segments line up with those of the others; the rest
+/// (e.g. `EXCLUDE`, MySQL's `INDEX`) are rendered more or less whole, so take
+/// no part in the widths lest they push every other constraint along.
+fn is_aligned(constraint: &TableConstraint) -> bool {
+    matches!(
+        constraint,
+        TableConstraint::Unique(_)
+            | TableConstraint::PrimaryKey(_)
+            | TableConstraint::ForeignKey(_)
+            | TableConstraint::Check(_)
+    )
+}
+
+/// Renders a list of columns over several lines, one column per line, using
+/// the configured separator one level further in:
+///
+/// ```sql
+///   , CONSTRAINT NAME UNIQUE (
+///         COLUMN_A
+///       , COLUMN_B
+///     )
+/// ```
+fn wrapped<T: Display>(columns: &[T], context: &Context) -> String {
+    let indent = context.indent();
+    let separator = context.options.separator.as_str().replace('\n', "\n    ");
+
+    format!(
+        "(\n    {}{}\n{})",
+        indent,
+        columns
+            .iter()
+            .map(|column| column.to_string())
+            .collect::<Vec<_>>()
+            .join(&separator),
+        indent,
+    )
+}
+
+/// The `CONSTRAINT {name}` prefix, for those constraints which are named.
+fn constraint_name_segment(name: &Option<Ident>, context: &Context) -> String {
+    name.as_ref()
+        .map(|name| format!("{} {}", context.keyword("CONSTRAINT"), name))
+        .unwrap_or_default()
+}
+
+/// MySQL's index name, as in `UNIQUE KEY {index_name} (column)`.
+fn index_name_segment(index_name: &Option<Ident>) -> String {
+    index_name
+        .as_ref()
+        .map(|index_name| index_name.to_string())
+        .unwrap_or_default()
+}
+
+/// The `USING {BTREE | HASH}` hint of a `PRIMARY KEY`/`UNIQUE` constraint.
+///
+/// MySQL accepts it either side of the column list; we always emit it after,
+/// as sqlparser mistakes a `USING` directly after `PRIMARY KEY`/`UNIQUE` for an
+/// index name and so can't read `PRIMARY KEY USING BTREE (id)` back in.
+fn index_type_segment(
+    index_type: &Option<IndexType>,
+    index_options: &[IndexOption],
+    context: &Context,
+) -> String {
+    index_type
+        .as_ref()
+        .or_else(|| {
+            index_options.iter().find_map(|option| match option {
+                IndexOption::Using(index_type) => Some(index_type),
+                _ => None,
+            })
+        })
+        .map(|index_type| format!("{} {}", context.keyword("USING"), index_type))
+        .unwrap_or_default()
+}
+
+/// Where nullability sits among a column's segments.
+const NULLABLE_SEGMENT: usize = 6;
+const COLUMN_SEGMENTS: usize = 10;
+
 /// Holds the components of a column definition about which we care for display
 /// purposes:
 ///
 /// ```sql
 /// CREATE TABLE table_name (
-///     NAME   TEXT        NOT NULL           DEFAULT ''
-///   , {name} {data_type} {options:nullable} {options:default}
+///     NAME   VARCHAR(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin                          NOT NULL   DEFAULT ''        ON UPDATE …   AUTO_INCREMENT
+///   , {name} {data_type} {charset}             {collation}         {generated} {srid}       {nullable} {default}         {on_update}   {attributes}
 /// )
 /// ;
 /// ```
+///
+/// Only the character set, collation, generation expression, SRID, `ON
+/// UPDATE` and attributes are left out of tables where no column has them.
 impl AlignedDisplay for ColumnDef {
-    fn segments(&self) -> Vec<String> {
-        let nullable = match self
-            .options
-            .iter()
-            .map(|option| &option.option)
-            .find(|option| {
-                matches!(option, ColumnOption::Null) || matches!(option, ColumnOption::NotNull)
-            }) {
-            Some(option) => option.to_string(),
-            None => "".to_string(),
-        };
-        let default = match self
-            .options
-            .iter()
-            .map(|option| &option.option)
-            .find(|option| matches!(option, ColumnOption::Default(_)))
-        {
-            Some(option) => option.to_string(),
-            None => "".to_string(),
+    fn segments(&self, context: &Context) -> Vec<String> {
+        let mut segments = vec![self.name.to_string(), context.data_type(&self.data_type)];
+
+        let segment = |wanted: fn(&ColumnOption) -> bool| {
+            self.options
+                .iter()
+                .map(|option| &option.option)
+                .find(|option| wanted(option))
+                .map(|option| column_option_segment(option, context))
+                .unwrap_or_default()
         };
 
-        vec![
-            self.name.to_string(),
-            self.data_type.to_string(),
-            nullable,
-            default,
-        ]
+        // MySQL insists on the character set, collation and generation
+        // expression coming straight after the data type, and on the character
+        // set coming before the collation, even when preserving the order of
+        // the rest.
+        segments.push(segment(|option| {
+            matches!(option, ColumnOption::CharacterSet(_))
+        }));
+        segments.push(segment(|option| {
+            matches!(option, ColumnOption::Collation(_))
+        }));
+
+        if context.options.preserve_option_order {
+            let mut options = self
+                .options
+                .iter()
+                .filter(|option| {
+                    !matches!(
+                        option.option,
+                        ColumnOption::CharacterSet(_) | ColumnOption::Collation(_)
+                    )
+                })
+                .map(|option| named_column_option_segment(option, context));
+            segments.resize(NULLABLE_SEGMENT, "".to_string());
+            segments.push(options.next().unwrap_or_default());
+            segments.push(options.collect::<Vec<_>>().join(" "));
+            segments.resize(COLUMN_SEGMENTS, "".to_string());
+
+            return segments;
+        }
+
+        segments.push(segment(|option| {
+            matches!(option, ColumnOption::Generated { .. })
+        }));
+        segments.push(segment(|option| matches!(option, ColumnOption::Srid(_))));
+        segments.push(segment(|option| {
+            matches!(option, ColumnOption::Null) || matches!(option, ColumnOption::NotNull)
+        }));
+        segments.push(segment(|option| matches!(option, ColumnOption::Default(_))));
+        segments.push(segment(|option| {
+            matches!(option, ColumnOption::OnUpdate(_))
+        }));
+        segments.push(
+            self.options
+                .iter()
+                .filter(|option| {
+                    !matches!(
+                        option.option,
+                        ColumnOption::CharacterSet(_)
+                            | ColumnOption::Collation(_)
+                            | ColumnOption::Generated { .. }
+                            | ColumnOption::Srid(_)
+                            | ColumnOption::Null
+                            | ColumnOption::NotNull
+                            | ColumnOption::Default(_)
+                            | ColumnOption::OnUpdate(_)
+                    )
+                })
+                .map(|option| named_column_option_segment(option, context))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+
+        segments
     }
 }
 
-/// Our nit-picking engine.
-///
-/// Maintains the internal `dialect` to be used for parsing the input.
-pub struct AntFarmer<T: Dialect> {
-    dialect: T,
+/// Renders a column option; those without a segment of their own (e.g.
+/// `REFERENCES`, `CHECK`, `COMMENT`) are rendered as `sqlparser` displays them,
+/// so that nothing is lost.
+fn column_option_segment(option: &ColumnOption, context: &Context) -> String {
+    match option {
+        ColumnOption::Null | ColumnOption::NotNull => context.keyword(&option.to_string()),
+        ColumnOption::Default(expr) => {
+            format!("{} {}", context.keyword("DEFAULT"), context.default(expr))
+        }
+        ColumnOption::OnUpdate(expr) => format!("{} {}", context.keyword("ON UPDATE"), expr),
+        ColumnOption::CharacterSet(name) => {
+            format!("{} {}", context.keyword("CHARACTER SET"), name)
+        }
+        ColumnOption::Collation(name) => format!("{} {}", context.keyword("COLLATE"), name),
+        ColumnOption::Srid(srid) => format!("{} {}", context.keyword("SRID"), srid),
+        _ => context.recase(&option.to_string()),
+    }
 }
 
-impl<T: Dialect> From<T> for AntFarmer<T> {
-    fn from(dialect: T) -> Self {
-        Self { dialect }
+/// As [`column_option_segment`], along with the `CONSTRAINT` naming the
+/// option, if any.
+fn named_column_option_segment(option: &ColumnOptionDef, context: &Context) -> String {
+    let segment = column_option_segment(&option.option, context);
+
+    match &option.name {
+        Some(name) => format!("{} {} {}", context.keyword("CONSTRAINT"), name, segment),
+        None => segment,
     }
 }
 
-impl<T: Dialect> AntFarmer<T> {
-    /// Parses the input SQL and outputs our "correctly" formatted version.
-    ///
-    /// Currently only `CREATE TABLE` is supported.
-    pub fn mierenneuke(&self, sql: &str) -> Result<String, ParserError> {
-        let ast = Parser::parse_sql(&self.dialect, sql)?;
+/// Whether `option` is a column's own `PRIMARY KEY` or `UNIQUE`.
+fn is_inline_key(option: &ColumnOptionDef) -> bool {
+    matches!(
+        option.option,
+        ColumnOption::PrimaryKey(_) | ColumnOption::Unique(_)
+    )
+}
 
-        let mut output = String::new();
+/// `table` with its columns' own `PRIMARY KEY` and `UNIQUE` moved into
+/// constraints after the rest, as per [`FormatOptions::hoist_inline_keys`].
+/// A `UNIQUE` without a name is given one, `uk_<table>_<column>`.
+fn hoisted_keys(table: &CreateTable) -> CreateTable {
+    let mut table = table.clone();
+    let table_name = match table.name.0.last().and_then(|part| part.as_ident()) {
+        Some(ident) => ident.value.clone(),
+        None => table.name.to_string(),
+    };
 
-        for statement in ast.iter() {
-            match statement {
-                Statement::CreateTable {
-                    name,
-                    columns,
-                    constraints,
-                    ..
-                } => {
-                    output += &format!("CREATE TABLE {} (\n", name);
-
-                    let columns = columns
-                        .iter()
-                        .map(|column| column.segments())
-                        .collect::<Vec<_>>();
+    let mut primary_key: Option<PrimaryKeyConstraint> = None;
+    let mut unique_keys = vec![];
+    for column in table.columns.iter_mut() {
+        let (keys, options) = mem::take(&mut column.options)
+            .into_iter()
+            .partition::<Vec<_>, _>(is_inline_key);
+        column.options = options;
 
-                    let constraints = constraints
-                        .iter()
-                        .map(|constraint| constraint.segments())
-                        .collect::<Vec<_>>();
-
-                    let column_widths = columns.iter().fold((0, 0, 0, 0), |acc, column| {
-                        (
-                            acc.0.max(column[0].len()),
-                            acc.1.max(column[1].len()),
-                            acc.2.max(column[2].len()),
-                            acc.3.max(column[3].len()),
-                        )
-                    });
-                    let constraint_widths =
-                        constraints
-                            .iter()
-                            .fold((0, 0, 0, 0, 0, 0, 0, 0), |acc, column| {
-                                (
-                                    acc.0.max(column[0].len()),
-                                    acc.1.max(column[1].len()),
-                                    acc.2.max(column[2].len()),
-                                    acc.3.max(column[3].len()),
-                                    acc.4.max(column[4].len()),
-                                    acc.5.max(column[5].len()),
-                                    acc.6.max(column[6].len()),
-                                    acc.7.max(column[7].len()),
-                                )
-                            });
-
-                    let columns = columns
-                        .iter()
-                        .map(|column| {
-                            format!(
-                                "{:<name_width$} {:<type_width$} {:>null_width$} {:<default_width$}",
-                                column[0], column[1], column[2], column[3],
-                                name_width=column_widths.0,
-                                type_width=column_widths.1,
-                                null_width=column_widths.2,
-                                default_width=column_widths.3,
-                            )
+        for ColumnOptionDef { name, option } in keys {
+            match option {
+                ColumnOption::PrimaryKey(key) => match &mut primary_key {
+                    // Not valid SQL, but there's no telling which was meant.
+                    Some(primary_key) => primary_key.columns.push(column.name.clone().into()),
+                    None => {
+                        primary_key = Some(PrimaryKeyConstraint {
+                            name: name.or(key.name),
+                            columns: vec![column.name.clone().into()],
+                            ..key
                         })
-                        .collect::<Vec<_>>()
-                        .join("\n  , ");
+                    }
+                },
+                ColumnOption::Unique(key) => {
+                    let name = name.or(key.name).unwrap_or_else(|| {
+                        Ident::new(format!("uk_{}_{}", table_name, column.name.value))
+                    });
+                    unique_keys.push(TableConstraint::Unique(UniqueConstraint {
+                        name: Some(name),
+                        columns: vec![column.name.clone().into()],
+                        ..key
+                    }));
+                }
+                _ => unreachable!("only keys are hoisted"),
+            }
+        }
+    }
 
-                    let constraints = constraints
-                        .iter()
-                        .map(|constraint| {
-                            format!(
-                                "{:<name_width$} {:<type_width$} {:<columns_width$} {:<three$} {:<four$} {:<five$} {:<six$} {:<seven$}",
-                                constraint[0],
-                                constraint[1],
-                                format!("({})", constraint[2]),
-                                constraint[3],
-                                constraint[4],
-                                if constraint[5].len() > 0 { format!("({})", constraint[5]) } else { "".to_owned() },
-                                constraint[6],
-                                constraint[7],
-                                name_width=constraint_widths.0,
-                                type_width=constraint_widths.1,
-                                columns_width=constraint_widths.2 + 2,
-                                three=constraint_widths.3,
-                                four=constraint_widths.4,
-                                five=constraint_widths.5 + 2,
-                                six=constraint_widths.6,
-                                seven=constraint_widths.7,
-                            )
-                            .trim()
-                            .to_owned()
-                        })
-                        .collect::<Vec<_>>()
-                        .join("\n  , ");
+    table
+        .constraints
+        .extend(primary_key.map(TableConstraint::PrimaryKey));
+    table.constraints.extend(unique_keys);
+
+    table
+}
+
+/// A lone column definition, e.g. that of `ALTER TABLE … MODIFY COLUMN`, with
+/// nothing to align against.
+fn column_definition(column: &ColumnDef, context: &Context) -> String {
+    compacted(&column.segments(context))
+}
+
+/// A row's segments with single spaces between them and no padding.
+fn compacted(segments: &[String]) -> String {
+    segments
+        .iter()
+        .filter(|segment| !segment.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Renders one operation of an `ALTER TABLE`; those without a dedicated arm
+/// are displayed as parsed.
+fn alter_table_operation(operation: &AlterTableOperation, context: &Context) -> String {
+    let column_def = |name: &Ident, data_type: &DataType, options: &[ColumnOption]| {
+        column_definition(
+            &ColumnDef {
+                name: name.clone(),
+                data_type: data_type.clone(),
+                options: options
+                    .iter()
+                    .map(|option| ColumnOptionDef {
+                        name: None,
+                        option: option.clone(),
+                    })
+                    .collect(),
+            },
+            context,
+        )
+    };
+    let position = |position: &Option<MySQLColumnPosition>| match position {
+        Some(position) => format!(" {}", context.recase(&position.to_string())),
+        None => "".to_string(),
+    };
+
+    match operation {
+        AlterTableOperation::DropColumn {
+            column_names,
+            if_exists,
+            drop_behavior,
+            ..
+        } => {
+            let mut output = context.keyword("DROP COLUMN");
+            if *if_exists {
+                output += &format!(" {}", context.keyword("IF EXISTS"));
+            }
+            output += &format!(
+                " {}",
+                column_names
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            if let Some(drop_behavior) = drop_behavior {
+                output += &format!(" {}", context.keyword(&drop_behavior.to_string()));
+            }
+
+            output
+        }
+        AlterTableOperation::ModifyColumn {
+            col_name,
+            data_type,
+            options,
+            column_position,
+        } => format!(
+            "{} {}{}",
+            context.keyword("MODIFY COLUMN"),
+            column_def(col_name, data_type, options),
+            position(column_position),
+        ),
+        AlterTableOperation::ChangeColumn {
+            old_name,
+            new_name,
+            data_type,
+            options,
+            column_position,
+        } => format!(
+            "{} {} {}{}",
+            context.keyword("CHANGE COLUMN"),
+            old_name,
+            column_def(new_name, data_type, options),
+            position(column_position),
+        ),
+        _ => context.recase(&operation.to_string()),
+    }
+}
+
+/// Renders a plain `INSERT … VALUES` a row per line, each value lined up with
+/// those in the same position in the other rows: numbers to the right,
+/// anything else to the left. Anything fancier (`ON DUPLICATE KEY UPDATE`,
+/// `RETURNING`, `INSERT … SELECT`, …) is left to the caller.
+fn insert_values(insert: &Insert, context: &Context) -> Option<String> {
+    let Some(SetExpr::Values(values)) = insert.source.as_ref().map(|query| query.body.as_ref())
+    else {
+        return None;
+    };
+    if insert.on.is_some() || insert.returning.is_some() || insert.insert_alias.is_some() {
+        return None;
+    }
+
+    // Everything up to and including `VALUES`.
+    let mut header = insert.clone();
+    if let Some(SetExpr::Values(values)) = header.source.as_mut().map(|query| query.body.as_mut()) {
+        values.rows.clear();
+    }
+
+    let rows = values
+        .rows
+        .iter()
+        .map(|row| row.iter().map(ToString::to_string).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let widths = widths(&rows, context.options.max_column_contribution);
+    // `NULL`s don't stop a column of numbers being numeric.
+    let is_number = |expr: &Expr| match expr {
+        Expr::Value(value) => matches!(value.value, Value::Number(..) | Value::Null),
+        Expr::UnaryOp { expr, .. } => {
+            matches!(expr.as_ref(), Expr::Value(value) if matches!(value.value, Value::Number(..)))
+        }
+        _ => false,
+    };
+    let numeric = (0..widths.len())
+        .map(|i| {
+            values
+                .rows
+                .iter()
+                .all(|row| row.get(i).is_none_or(is_number))
+        })
+        .collect::<Vec<_>>();
 
-                    output += &format!("    {}\n", columns);
-                    if constraints.len() > 0 {
-                        output += &format!("  , {}\n", constraints);
+    let prefix = if values.explicit_row {
+        context.keyword("ROW")
+    } else {
+        "".to_string()
+    };
+    let lines = rows
+        .iter()
+        .map(|row| {
+            let last = row.len().saturating_sub(1);
+            let values = row
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let width = widths[i];
+                    match (numeric[i], i == last) {
+                        (true, true) => format!("{:>width$}", value),
+                        (true, false) => format!("{:>width$},", value),
+                        (false, true) => value.clone(),
+                        (false, false) => {
+                            format!("{:<width$}", format!("{},", value), width = width + 1)
+                        }
                     }
-                    output += ")\n;";
+                })
+                .collect::<Vec<_>>();
+            format!("{}({})", prefix, values.join(" "))
+        })
+        .collect::<Vec<_>>();
+
+    Some(format!(
+        "{}\n{}{}\n;",
+        context.recase(header.to_string().trim_end()),
+        context.indent(),
+        lines.join(context.options.separator.as_str())
+    ))
+}
+
+/// The width of each segment, across all rows. A wrapped segment has a line to
+/// itself, and one longer than `max` (see
+/// [`FormatOptions::max_column_contribution`]) overflows its line, so neither
+/// counts.
+fn widths(rows: &[Vec<String>], max: Option<usize>) -> Vec<usize> {
+    rows.iter().fold(vec![], |widths, row| {
+        row.iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                let width = if segment.contains('\n') || max.is_some_and(|max| segment.len() > max)
+                {
+                    0
+                } else {
+                    segment.len()
+                };
+                widths.get(i).copied().unwrap_or(0).max(width)
+            })
+            .collect()
+    })
+}
+
+/// Joins `lines` with `separator`, appending each line's comment in a column
+/// of its own; any comma the separator leaves at the end of a line comes
+/// before the comment.
+fn commented(lines: &[String], comments: &[Option<String>], separator: &str) -> String {
+    let (head, tail) = separator.split_once('\n').unwrap_or((separator, ""));
+
+    let lines = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = if i + 1 < lines.len() {
+                format!("{}{}", line, head)
+            } else {
+                line.clone()
+            };
+            line.trim_end().to_owned()
+        })
+        .collect::<Vec<_>>();
+    // Only the last line of a wrapped constraint is followed by its comment.
+    let last_line_len = |line: &String| line.rsplit('\n').next().unwrap_or("").len();
+    let width = lines.iter().map(last_line_len).max().unwrap_or(0);
+
+    lines
+        .iter()
+        .zip(comments)
+        .map(|(line, comment)| match comment {
+            Some(comment) => format!(
+                "{}{} {}",
+                line,
+                " ".repeat(width - last_line_len(line)),
+                comment
+            ),
+            None => line.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}", tail))
+}
+
+/// Where a table option comes after the closing parenthesis: the engine, then
+/// the character set, then the collation, then anything else as written.
+fn table_option_rank(option: &SqlOption) -> usize {
+    let key = match option {
+        SqlOption::KeyValue { key, .. } => key.value.to_uppercase(),
+        SqlOption::NamedParenthesizedList(list) => list.key.value.to_uppercase(),
+        _ => "".to_string(),
+    };
+
+    if key == "ENGINE" {
+        0
+    } else if key.contains("CHARSET") || key.contains("CHARACTER SET") {
+        1
+    } else if key.contains("COLLATE") {
+        2
+    } else {
+        3
+    }
+}
+
+/// Renders a table option the way `mysqldump` does, i.e. `KEY=value`.
+fn table_option(option: &SqlOption, context: &Context) -> String {
+    match option {
+        SqlOption::KeyValue { key, value } => format!("{}={}", context.keyword(&key.value), value),
+        SqlOption::NamedParenthesizedList(list) if list.values.is_empty() => match &list.name {
+            Some(name) => format!("{}={}", context.keyword(&list.key.value), name),
+            None => context.keyword(&list.key.value),
+        },
+        _ => context.recase(&option.to_string()),
+    }
+}
+
+/// The byte offset in `sql` of `location`, given the offset at which each of
+/// its lines starts; the tokenizer counts columns in characters.
+fn byte_offset(sql: &str, line_starts: &[usize], location: Location) -> usize {
+    let Some(&line_start) = (location.line as usize)
+        .checked_sub(1)
+        .and_then(|line| line_starts.get(line))
+    else {
+        return sql.len();
+    };
+
+    line_start
+        + sql[line_start..]
+            .chars()
+            .take((location.column as usize).saturating_sub(1))
+            .map(char::len_utf8)
+            .sum::<usize>()
+}
+
+/// Whether `name` is the table [`FormatOptions::only_tables`] calls `wanted`,
+/// either in full or by its unqualified, unquoted name.
+fn table_named(name: &ObjectName, wanted: &str) -> bool {
+    name.to_string() == wanted
+        || name
+            .0
+            .last()
+            .and_then(|part| part.as_ident())
+            .is_some_and(|ident| ident.value == wanted)
+}
+
+/// The words of a statement's `sql` which [`Context::recase`] mustn't mistake
+/// for keywords, as they're unquoted identifiers, e.g. a table called `USER`;
+/// in uppercase, as that's all it touches.
+///
+/// `sqlparser` renders keywords in uppercase and identifiers as written, so
+/// with every keyword-like word lowercased, those still lowercase once the
+/// statement is parsed and rendered again are the ones it took as identifiers.
+fn identifiers(dialect: &dyn Dialect, sql: &str) -> HashSet<String> {
+    let words = |sql: &str| {
+        Tokenizer::new(dialect, sql)
+            .with_unescape(false)
+            .tokenize()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|token| match token {
+                Token::Word(word)
+                    if word.quote_style.is_none() && word.keyword != Keyword::NoKeyword =>
+                {
+                    Ok(word.value)
                 }
-                _ => todo!(),
-            }
+                _ => Err(token),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let lowercased = words(sql)
+        .into_iter()
+        .map(|word| match word {
+            Ok(word) => word.to_lowercase(),
+            Err(token) => token.to_string(),
+        })
+        .collect::<String>();
+    let Ok(statements) = parse_statements(dialect, &lowercased) else {
+        return HashSet::new();
+    };
+
+    statements
+        .iter()
+        .flat_map(|(statement, _)| words(&statement.to_string()))
+        .filter_map(Result::ok)
+        .filter(|word| *word != word.to_uppercase())
+        .map(|word| word.to_uppercase())
+        .collect()
+}
+
+/// Parses `sql` as `dialect`, tolerating trailing commas, a statement at a
+/// time so that each is paired with the [`Source`] of exactly the tokens it
+/// was parsed from; a `;` within a statement (e.g. in a trigger's `BEGIN …
+/// END`) doesn't end it.
+fn parse_statements(
+    dialect: &dyn Dialect,
+    sql: &str,
+) -> Result<Vec<(Statement, Source)>, ParserError> {
+    let tokens = Tokenizer::new(dialect, sql).tokenize_with_location()?;
+    let line_starts = iter::once(0)
+        .chain(sql.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<_>>();
+    let mut parser = Parser::new(dialect)
+        .with_options(ParserOptions::new().with_trailing_commas(true))
+        .with_tokens_with_locations(tokens.clone());
+
+    let mut statements = vec![];
+    // Where the text of the next statement, along with any comments before
+    // it, starts.
+    let mut start = 0;
+    loop {
+        while parser.consume_token(&Token::SemiColon) {}
+        if parser.peek_token_ref().token == Token::EOF {
+            break;
         }
 
-        Ok(output)
+        let first = parser.index();
+        let statement = parser.parse_statement()?;
+        let terminated = parser.consume_token(&Token::SemiColon);
+        let last = parser.index();
+
+        let mut source = Source::from_tokens(&tokens[first..last.min(tokens.len())]);
+        let end = match tokens.get(last.wrapping_sub(1)) {
+            Some(semicolon) if terminated => byte_offset(sql, &line_starts, semicolon.span.end),
+            _ => sql.len(),
+        };
+        source.text = sql[start..end].trim().to_string();
+        source.unterminated = !terminated;
+        source.conditional = source.text.contains("/*!");
+        start = end;
+        statements.push((statement, source));
+
+        // As `Parser::parse_statements`, which stops at an `END` so as to
+        // parse the statements of a block.
+        if !terminated {
+            match &parser.peek_token_ref().token {
+                Token::EOF => break,
+                Token::Word(word) if word.keyword == Keyword::END => break,
+                _ => return parser.expected("end of statement", parser.peek_token()),
+            }
+        }
     }
+
+    Ok(statements)
 }
 
-#[cfg(test)]
-mod tests {
-    use sqlparser::dialect::MySqlDialect;
+/// What to put after the last column or constraint, as per
+/// [`FormatOptions::trailing_comma_last_item`]: the separator's comma, if it
+/// has one and the dialect will parse it there.
+fn last_item_comma<'a>(dialect: &dyn Dialect, options: &'a FormatOptions) -> Option<&'a str> {
+    let (head, _) = options.separator.as_str().split_once('\n')?;
 
-    use super::*;
+    (options.trailing_comma_last_item
+        && dialect.supports_column_definition_trailing_commas()
+        && head.trim_end().ends_with(','))
+    .then_some(head)
+}
 
-    #[test]
-    fn test_basic_create_table() {
-        let sql = r#"cReAtE tAbLe operators_create_consumers (operator_api_key_id    int(11)    NOT NULL, operator_ip_address_id int(11)   nOt NuLl, create_consumers JSON nOt NuLl, created_date datetime nOt NuLl dEfAuLt CURRENT_TIMESTAMP());"#;
-        let ant_farmer = AntFarmer::from(MySqlDialect {});
-        let expected = r#"CREATE TABLE operators_create_consumers (
-    operator_api_key_id    INT(11)  NOT NULL                            
-  , operator_ip_address_id INT(11)  NOT NULL                            
-  , create_consumers       JSON     NOT NULL                            
-  , created_date           datetime NOT NULL DEFAULT CURRENT_TIMESTAMP()
-)
-;"#;
+/// Picks the nits in a statement which don't affect how it's formatted.
+fn statement_warnings(
+    statement: &Statement,
+    dialect: &dyn Dialect,
+    options: &FormatOptions,
+) -> Vec<Warning> {
+    let Statement::CreateTable(CreateTable {
+        name,
+        columns,
+        constraints,
+        ..
+    }) = statement
+    else {
+        return vec![];
+    };
 
-        let result = ant_farmer.mierenneuke(sql).unwrap();
+    let mut warnings = vec![];
+    if options.trailing_comma_last_item && last_item_comma(dialect, options).is_none() {
+        warnings.push(Warning::TrailingCommaIgnored {
+            table: name.to_string(),
+        });
+    }
 
-        assert_eq!(result, expected);
+    warnings.extend(
+        constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                TableConstraint::ForeignKey(foreign_key) => Some(foreign_key),
+                _ => None,
+            })
+            .flat_map(|foreign_key| {
+                foreign_key
+                    .columns
+                    .iter()
+                    .filter(|column| {
+                        !columns
+                            .iter()
+                            .any(|declared| declared.name.value.eq_ignore_ascii_case(&column.value))
+                    })
+                    .map(|column| Warning::UnknownForeignKeyColumn {
+                        table: name.to_string(),
+                        constraint: foreign_key.name.as_ref().map(ToString::to_string),
+                        column: column.to_string(),
+                    })
+            }),
+    );
+
+    warnings
+}
+
+/// Anything which can stand in for a [`Dialect`]: any concrete dialect, or a
+/// `dyn Dialect` chosen at runtime (see [`AntFarmer::try_from_dialect_name`]).
+pub trait AsDialect {
+    fn as_dialect(&self) -> &dyn Dialect;
+}
+
+impl<T: Dialect> AsDialect for T {
+    fn as_dialect(&self) -> &dyn Dialect {
+        self
     }
+}
 
-    #[test]
-    fn test_create_table_null() {
-        let sql = r#"cReAtE tAbLe operators_create_consumers (operator_api_key_id    int(11)    NOT NULL, operator_ip_address_id int(11)   nOt NuLl, create_consumers JSON NuLl, created_date datetime nOt NuLl dEfAuLt CURRENT_TIMESTAMP());"#;
-        let ant_farmer = AntFarmer::from(MySqlDialect {});
-        let expected = r#"CREATE TABLE operators_create_consumers (
-    operator_api_key_id    INT(11)  NOT NULL                            
-  , operator_ip_address_id INT(11)  NOT NULL                            
-  , create_consumers       JSON         NULL                            
-  , created_date           datetime NOT NULL DEFAULT CURRENT_TIMESTAMP()
-)
-;"#;
+impl AsDialect for dyn Dialect {
+    fn as_dialect(&self) -> &dyn Dialect {
+        self
+    }
+}
 
-        let result = ant_farmer.mierenneuke(sql).unwrap();
+/// Our nit-picking engine.
+///
+/// Maintains the internal `dialect` to be used for parsing the input and the
+/// `options` governing the output.
+pub struct AntFarmer<T: AsDialect + ?Sized> {
+    options: FormatOptions,
+    // Last, so that an `AntFarmer<T>` can be unsized to `AntFarmer<dyn Dialect>`.
+    dialect: T,
+}
 
-        assert_eq!(result, expected);
+impl<T: Dialect> From<T> for AntFarmer<T> {
+    fn from(dialect: T) -> Self {
+        Self {
+            options: FormatOptions::default(),
+            dialect,
+        }
+    }
+}
+
+impl AntFarmer<dyn Dialect> {
+    /// The names [`AntFarmer::try_from_dialect_name`] understands.
+    pub const DIALECT_NAMES: [&'static str; 5] =
+        ["mysql", "postgres", "sqlite", "mssql", "generic"];
+
+    /// Builds a farmer for one of the dialects we know by name: `generic`,
+    /// `mssql`, `mysql`, `postgres` or `sqlite`.
+    ///
+    /// This is the one place names are mapped to dialects, for the benefit of
+    /// the CLI and anything else configured with strings.
+    pub fn try_from_dialect_name(name: &str) -> Result<Box<Self>, AntError> {
+        Ok(match name {
+            "generic" => Box::new(AntFarmer::from(GenericDialect {})),
+            "mssql" => Box::new(AntFarmer::from(MsSqlDialect {})),
+            "mysql" => Box::new(AntFarmer::from(MySqlDialect {})),
+            "postgres" => Box::new(AntFarmer::from(PostgreSqlDialect {})),
+            "sqlite" => Box::new(AntFarmer::from(SQLiteDialect {})),
+            _ => return Err(AntError::UnknownDialect(name.to_string())),
+        })
+    }
+}
+
+impl<T: AsDialect + ?Sized> AntFarmer<T> {
+    /// Replaces the default [`FormatOptions`].
+    pub fn with_options(mut self, options: FormatOptions) -> Self
+    where
+        Self: Sized,
+    {
+        self.options = options;
+        self
+    }
+
+    /// As [`AntFarmer::with_options`], for farmers which have already been
+    /// boxed, e.g. by [`AntFarmer::try_from_dialect_name`].
+    pub fn set_options(&mut self, options: FormatOptions) {
+        self.options = options;
+    }
+
+    /// Parses the input SQL and outputs our "correctly" formatted version.
+    ///
+    /// Currently `CREATE TABLE` is aligned, while everything else (`CREATE
+    /// SCHEMA`, `SET`, `TRUNCATE`, …) is passed through on a single line.
+    /// Statements are separated by a blank line.
+    ///
+    /// Trailing commas (e.g. `col INT,)`, as found in some dumps) are accepted
+    /// regardless of dialect; they never make it into the output.
+    pub fn format(&self, sql: &str) -> Result<String, AntError> {
+        let statements = self.format_iter(sql).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(statements.join("\n\n"))
+    }
+
+    /// As [`AntFarmer::format`], but yields each statement as it's formatted,
+    /// so that a large schema can be written out a statement at a time.
+    ///
+    /// The input is parsed up front: if that fails, the error is the only
+    /// item.
+    pub fn format_iter<'a>(
+        &'a self,
+        sql: &'a str,
+    ) -> impl Iterator<Item = Result<String, AntError>> + 'a {
+        let (parsed, error) = match self.parse(sql) {
+            Ok(parsed) => (Some(parsed), None),
+            Err(err) => (None, Some(Err(err))),
+        };
+
+        error.into_iter().chain(
+            parsed
+                .into_iter()
+                .flat_map(|(ast, sources)| self.format_parsed(ast, sources))
+                .map(Ok),
+        )
+    }
+
+    /// As [`AntFarmer::format`], along with anything in the input worth a
+    /// [`Warning`].
+    pub fn format_with_warnings(&self, sql: &str) -> Result<(String, Vec<Warning>), AntError> {
+        let (ast, sources) = self.parse(sql)?;
+
+        let mut warnings = ast
+            .iter()
+            .flat_map(|statement| {
+                statement_warnings(statement, self.dialect.as_dialect(), &self.options)
+            })
+            .collect::<Vec<_>>();
+        warnings.extend(
+            self.options
+                .only_tables
+                .iter()
+                .filter(|wanted| {
+                    !ast.iter().any(|statement| {
+                        matches!(
+                            statement,
+                            Statement::CreateTable(CreateTable { name, .. }) if table_named(name, wanted)
+                        )
+                    })
+                })
+                .map(|wanted| Warning::UnknownTable {
+                    table: wanted.clone(),
+                }),
+        );
+        let statements = self.format_parsed(&ast, sources).collect::<Vec<_>>();
+
+        Ok((statements.join("\n\n"), warnings))
+    }
+
+    /// Formats the file at `path`, rewriting it (as UTF-8, with a final
+    /// newline) only if that changes it; returns whether it did.
+    pub fn format_file_in_place<P: AsRef<Path>>(&self, path: P) -> Result<bool, AntError> {
+        let path = path.as_ref();
+        let sql = fs::read_to_string(path)?;
+
+        self.format_text_in_place(path, &sql)
+            .map(|(changed, _)| changed)
+    }
+
+    /// As [`AntFarmer::format_file_in_place`], for the contents of `path`
+    /// already read and decoded (e.g. from Latin-1) as `sql`, along with
+    /// anything in it worth a [`Warning`].
+    pub fn format_text_in_place<P: AsRef<Path>>(
+        &self,
+        path: P,
+        sql: &str,
+    ) -> Result<(bool, Vec<Warning>), AntError> {
+        let (formatted, warnings) =
+            self.format_with_warnings(sql.strip_prefix('\u{feff}').unwrap_or(sql))?;
+        let formatted = formatted + "\n";
+        if formatted == sql {
+            return Ok((false, warnings));
+        }
+        fs::write(path, formatted)?;
+
+        Ok((true, warnings))
+    }
+
+    /// As [`AntFarmer::format`], along with the parsed statements, so that
+    /// callers wanting both needn't parse the input twice.
+    pub fn format_with_ast(&self, sql: &str) -> Result<(String, Vec<Statement>), AntError> {
+        let (ast, sources) = self.parse(sql)?;
+
+        let statements = self.format_parsed(&ast, sources).collect::<Vec<_>>();
+
+        Ok((statements.join("\n\n"), ast))
+    }
+
+    fn format_parsed<'a, S: Borrow<Statement> + 'a>(
+        &'a self,
+        ast: impl IntoIterator<Item = S> + 'a,
+        sources: Vec<Source>,
+    ) -> impl Iterator<Item = String> + 'a {
+        let statements = ast
+            .into_iter()
+            .zip(
+                sources
+                    .into_iter()
+                    .chain(iter::repeat_with(Source::default)),
+            )
+            .collect::<Vec<_>>();
+        let widths = self
+            .options
+            .global_alignment
+            .then(|| self.global_widths(&statements));
+
+        statements.into_iter().map(move |(statement, source)| {
+            if (source.conditional || self.passes_through(statement.borrow()))
+                && !source.text.is_empty()
+            {
+                return source.text;
+            }
+
+            // Uppercase keywords are left as they are anyway.
+            let identifiers = match self.options.keyword_case {
+                KeywordCase::Upper => HashSet::new(),
+                _ => identifiers(self.dialect.as_dialect(), &source.text),
+            };
+            let context = Context {
+                dialect: self.dialect.as_dialect(),
+                options: &self.options,
+                source: &source,
+                widths: widths.as_ref(),
+                identifiers: &identifiers,
+            };
+
+            let output = self.format_statement(statement.borrow(), &context);
+            match output.strip_suffix("\n;") {
+                Some(unterminated) if source.unterminated => unterminated.to_string(),
+                _ => output,
+            }
+        })
+    }
+
+    /// Whether `statement` is to be left as written, as per
+    /// [`FormatOptions::only_tables`].
+    fn passes_through(&self, statement: &Statement) -> bool {
+        let wanted = &self.options.only_tables;
+        !wanted.is_empty()
+            && !matches!(
+                statement,
+                Statement::CreateTable(CreateTable { name, .. })
+                    if wanted.iter().any(|wanted| table_named(name, wanted))
+            )
+    }
+
+    /// The widths of the segments of every `CREATE TABLE` in `statements`
+    /// taken together, so that they can all share them; those left as written
+    /// don't count.
+    fn global_widths<S: Borrow<Statement>>(&self, statements: &[(S, Source)]) -> TableWidths {
+        let mut columns = vec![];
+        let mut constraints = vec![];
+        for (statement, source) in statements {
+            if source.conditional || self.passes_through(statement.borrow()) {
+                continue;
+            }
+            if let Statement::CreateTable(table) = statement.borrow() {
+                let table = if self.options.hoist_inline_keys {
+                    Cow::Owned(hoisted_keys(table))
+                } else {
+                    Cow::Borrowed(table)
+                };
+                let context = Context {
+                    dialect: self.dialect.as_dialect(),
+                    options: &self.options,
+                    source,
+                    widths: None,
+                    // Casing doesn't change widths.
+                    identifiers: &HashSet::new(),
+                };
+                columns.extend(table.columns.iter().map(|column| column.segments(&context)));
+                constraints.extend(
+                    table
+                        .constraints
+                        .iter()
+                        .filter(|constraint| is_aligned(constraint))
+                        .map(|constraint| constraint.segments(&context)),
+                );
+            }
+        }
+
+        TableWidths {
+            columns: widths(&columns, self.options.max_column_contribution),
+            constraints: widths(&constraints, self.options.max_column_contribution),
+        }
+    }
+
+    /// Parses the input into statements, alongside what the parser doesn't
+    /// keep of each.
+    fn parse(&self, sql: &str) -> Result<(Vec<Statement>, Vec<Source>), AntError> {
+        if let Separator::Custom(separator) = &self.options.separator {
+            if !separator.contains('\n') {
+                return Err(AntError::InvalidSeparator(separator.clone()));
+            }
+        }
+
+        let statements = match parse_statements(self.dialect.as_dialect(), sql) {
+            Ok(statements) => statements,
+            // Most often, the input was written for MySQL and the dialect is
+            // the wrong one; say so, rather than leave it to the parser error.
+            Err(err)
+                if !self.dialect.as_dialect().is::<MySqlDialect>()
+                    && parse_statements(&MySqlDialect {}, sql).is_ok() =>
+            {
+                return Err(AntError::MySqlOnly(err));
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(statements.into_iter().unzip())
+    }
+
+    /// The original, Dutch, name of [`AntFarmer::format`]: to *mierenneuken*
+    /// is to nit-pick.
+    #[deprecated(note = "use `format` instead")]
+    pub fn mierenneuke(&self, sql: &str) -> Result<String, AntError> {
+        self.format(sql)
+    }
+
+    fn format_statement(&self, statement: &Statement, context: &Context) -> String {
+        match statement {
+            Statement::CreateTable(table)
+                if self.options.hoist_inline_keys
+                    && table
+                        .columns
+                        .iter()
+                        .any(|column| column.options.iter().any(is_inline_key)) =>
+            {
+                self.format_statement(&Statement::CreateTable(hoisted_keys(table)), context)
+            }
+            Statement::CreateTable(CreateTable {
+                name,
+                columns,
+                constraints,
+                table_options,
+                ..
+            }) => {
+                let mut output = format!("{} {} (\n", context.keyword("CREATE TABLE"), name);
+
+                let columns = columns
+                    .iter()
+                    .map(|column| column.segments(context))
+                    .collect::<Vec<_>>();
+
+                let constraints = constraints
+                    .iter()
+                    .map(|constraint| (is_aligned(constraint), constraint.segments(context)))
+                    .collect::<Vec<_>>();
+                let aligned_constraints = constraints
+                    .iter()
+                    .filter(|(aligned, _)| *aligned)
+                    .map(|(_, segments)| segments.clone())
+                    .collect::<Vec<_>>();
+
+                let (column_widths, constraint_widths) = match context.widths {
+                    Some(widths) => (widths.columns.clone(), widths.constraints.clone()),
+                    None => (
+                        widths(&columns, self.options.max_column_contribution),
+                        widths(&aligned_constraints, self.options.max_column_contribution),
+                    ),
+                };
+
+                let columns = columns
+                    .iter()
+                    .map(|column| {
+                        if self.options.compact {
+                            return compacted(column);
+                        }
+
+                        column
+                            .iter()
+                            .zip(column_widths.iter())
+                            .enumerate()
+                            .filter(|(_, (segment, width))| **width > 0 || !segment.is_empty())
+                            .map(|(i, (segment, width))| {
+                                // Nullability is right-aligned so that `NULL` lines
+                                // up with the `NULL` of `NOT NULL`; in source order
+                                // there's no telling what's in that segment.
+                                if i == NULLABLE_SEGMENT && !self.options.preserve_option_order {
+                                    format!("{:>width$}", segment)
+                                } else {
+                                    format!("{:<width$}", segment)
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect::<Vec<_>>();
+
+                let constraints = constraints
+                    .iter()
+                    .map(|(aligned, constraint)| {
+                        // The rest aren't in the widths, so have nothing to line
+                        // up with.
+                        if self.options.compact || !aligned {
+                            return compacted(constraint);
+                        }
+
+                        constraint
+                            .iter()
+                            .zip(constraint_widths.iter())
+                            .filter(|(segment, width)| **width > 0 || !segment.is_empty())
+                            .map(|(segment, width)| format!("{:<width$}", segment))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                            .trim_end()
+                            .to_owned()
+                    })
+                    .collect::<Vec<_>>();
+
+                let comments = if self.options.preserve_comments {
+                    let source = context.source;
+                    (0..columns.len())
+                        .map(|i| source.column_comments.get(i).cloned().flatten())
+                        .chain(
+                            (0..constraints.len())
+                                .map(|i| source.constraint_comments.get(i).cloned().flatten()),
+                        )
+                        .collect()
+                } else {
+                    vec![]
+                };
+
+                // Padding is only kept where something follows it on the line.
+                let separator = self.options.separator.as_str();
+                let mut lines = columns
+                    .into_iter()
+                    .chain(constraints)
+                    .map(|line| line.trim_end().to_owned())
+                    .collect::<Vec<_>>();
+                if let (Some(comma), Some(last)) = (
+                    last_item_comma(context.dialect, &self.options),
+                    lines.last_mut(),
+                ) {
+                    *last += comma.trim_end();
+                }
+
+                let body = if comments.iter().any(Option::is_some) {
+                    commented(&lines, &comments, separator)
+                } else {
+                    lines.join(separator)
+                };
+                output += &format!("{}{}\n", context.indent(), body);
+                output += ")";
+
+                match table_options {
+                    CreateTableOptions::None => {}
+                    CreateTableOptions::Plain(options) => {
+                        let mut options = options.iter().collect::<Vec<_>>();
+                        options.sort_by_key(|option| table_option_rank(option));
+                        for option in options {
+                            output += &format!(" {}", table_option(option, context));
+                        }
+                    }
+                    // E.g. SQL Server's `WITH (DATA_COMPRESSION = PAGE)`; its
+                    // `ON [filegroup]` isn't something sqlparser can parse.
+                    _ => output += &format!(" {}", context.recase(&table_options.to_string())),
+                }
+                output += "\n;";
+
+                output
+            }
+            Statement::AlterTable(AlterTable {
+                name,
+                if_exists,
+                only,
+                operations,
+                location: None,
+                on_cluster: None,
+                table_type: None,
+                ..
+            }) => {
+                let mut output = context.keyword("ALTER TABLE");
+                if *if_exists {
+                    output += &format!(" {}", context.keyword("IF EXISTS"));
+                }
+                if *only {
+                    output += &format!(" {}", context.keyword("ONLY"));
+                }
+                output += &format!(" {}\n", name);
+
+                let operations = operations
+                    .iter()
+                    .map(|operation| alter_table_operation(operation, context))
+                    .collect::<Vec<_>>();
+
+                output += &format!(
+                    "{}{}\n;",
+                    context.indent(),
+                    operations.join(self.options.separator.as_str())
+                );
+
+                output
+            }
+            Statement::Insert(insert) if self.options.align_insert_values => {
+                match insert_values(insert, context) {
+                    Some(output) => output,
+                    None => format!("{}\n;", context.recase(&statement.to_string())),
+                }
+            }
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                if_exists,
+            } => {
+                let mut output = context.keyword("COMMENT");
+                if *if_exists {
+                    output += &format!(" {}", context.keyword("IF EXISTS"));
+                }
+                // `sqlparser` displays the comment without escaping any quotes
+                // in it.
+                let comment = match comment {
+                    Some(comment) => Value::SingleQuotedString(comment.clone()).to_string(),
+                    None => context.keyword("NULL"),
+                };
+
+                format!(
+                    "{output} {} {} {object_name} {} {comment}\n;",
+                    context.keyword("ON"),
+                    context.keyword(&object_type.to_string()),
+                    context.keyword("IS"),
+                )
+            }
+            // Nothing to align (e.g. `CREATE SCHEMA`, `SET`, `TRUNCATE`); just
+            // tidy up the keywords.
+            _ => format!("{}\n;", context.recase(&statement.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::dialect::BigQueryDialect;
+    use std::env;
+
+    #[test]
+    fn test_create_table_lowercase_keywords() {
+        let sql = r#"CREATE TABLE operators_create_consumers (operator_api_key_id int(11) NOT NULL, created_date datetime NOT NULL DEFAULT CURRENT_TIMESTAMP(), CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id) REFERENCES api_keys (id) ON DELETE CASCADE);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            ..Default::default()
+        });
+        let expected = r#"create table operators_create_consumers (
+    operator_api_key_id INT(11)  not null
+  , created_date        DATETIME not null default CURRENT_TIMESTAMP()
+  , constraint fk_operators_create_consumers_operator_api_key_id foreign key (operator_api_key_id) references api_keys (id) on delete cascade
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_preserve_keywords() {
+        let sql = r#"cReAtE tAbLe operators_create_consumers (operator_api_key_id    int(11)    not null, operator_ip_address_id int(11)   nOt NuLl, created_date datetime nOt NuLl dEfAuLt CURRENT_TIMESTAMP() , Constraint uq_operator_api_key_id_operator_ip_address_id Unique (operator_api_key_id, operator_ip_address_id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            keyword_case: KeywordCase::Preserve,
+            ..Default::default()
+        });
+        let expected = r#"cReAtE tAbLe operators_create_consumers (
+    operator_api_key_id    INT(11)  not null
+  , operator_ip_address_id INT(11)  not null
+  , created_date           DATETIME not null dEfAuLt CURRENT_TIMESTAMP()
+  , Constraint uq_operator_api_key_id_operator_ip_address_id Unique (operator_api_key_id, operator_ip_address_id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_preserve_option_order() {
+        let sql = r#"CREATE TABLE operators_create_consumers (id int(11) DEFAULT 0 NOT NULL, create_consumers JSON NULL, created_date datetime NOT NULL DEFAULT CURRENT_TIMESTAMP());"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            preserve_option_order: true,
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE operators_create_consumers (
+    id               INT(11)  DEFAULT 0 NOT NULL
+  , create_consumers JSON     NULL
+  , created_date     DATETIME NOT NULL  DEFAULT CURRENT_TIMESTAMP()
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_collation_before_character_set() {
+        let sql = r#"CREATE TABLE people (name VARCHAR(64) COLLATE utf8mb4_bin CHARACTER SET utf8mb4 NOT NULL, bio TEXT NULL COLLATE latin1_bin CHARACTER SET latin1);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+        let expected = r#"CREATE TABLE people (
+    name VARCHAR(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin NOT NULL
+  , bio  TEXT        CHARACTER SET latin1  COLLATE latin1_bin      NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+
+        let ant_farmer =
+            ant_farmer.with_options(FormatOptions::default().with_preserve_option_order(true));
+        let expected = r#"CREATE TABLE people (
+    name VARCHAR(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin NOT NULL
+  , bio  TEXT        CHARACTER SET latin1  COLLATE latin1_bin  NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_trailing_separator() {
+        let sql = r#"CREATE TABLE operators_create_consumers (operator_api_key_id int(11) NOT NULL, create_consumers JSON NULL, CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id) REFERENCES api_keys (id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            separator: Separator::Trailing,
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE operators_create_consumers (
+    operator_api_key_id INT(11) NOT NULL,
+    create_consumers    JSON        NULL,
+    CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id) REFERENCES api_keys (id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_custom_separator() {
+        let sql = r#"CREATE TABLE operators_create_consumers (operator_api_key_id int(11) NOT NULL, create_consumers JSON NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            separator: Separator::Custom("\n    , ".to_string()),
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE operators_create_consumers (
+      operator_api_key_id INT(11) NOT NULL
+    , create_consumers    JSON        NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            separator: Separator::Custom(", ".to_string()),
+            ..Default::default()
+        });
+
+        let result = ant_farmer.format(sql);
+
+        assert!(matches!(result, Err(AntError::InvalidSeparator(_))));
+    }
+
+    #[test]
+    fn test_create_schema_and_database() {
+        let sql = r#"CREATE SCHEMA IF NOT EXISTS app; create   database IF NOT EXISTS reporting; CREATE TABLE app.operators_create_consumers (operator_api_key_id int(11) NOT NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            ..Default::default()
+        });
+        let expected = r#"create schema if not exists app
+;
+
+create database if not exists reporting
+;
+
+create table app.operators_create_consumers (
+    operator_api_key_id INT(11) not null
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_mierenneuke() {
+        let sql =
+            r#"cReAtE tAbLe operators_create_consumers (operator_api_key_id int(11) NOT NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+
+        assert_eq!(
+            ant_farmer.mierenneuke(sql).unwrap(),
+            ant_farmer.format(sql).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_create_table_first_row_aligned() {
+        let sql = r#"CREATE TABLE operators_create_consumers (operator_api_key_id int(11) NOT NULL, create_consumers JSON NULL, CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id) REFERENCES api_keys (id));"#;
+
+        for separator in [
+            Separator::Leading,
+            Separator::Trailing,
+            Separator::Custom("\n    , ".to_string()),
+            Separator::Custom(",\n\t".to_string()),
+        ] {
+            let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+                separator: separator.clone(),
+                ..Default::default()
+            });
+
+            let result = ant_farmer.format(sql).unwrap();
+            let lines = result.lines().collect::<Vec<_>>();
+            let starts = lines[1..lines.len() - 2]
+                .iter()
+                .map(|line| line.find(|c: char| c.is_alphanumeric()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(starts.len(), 3);
+            assert!(
+                starts.iter().all(|start| *start == starts[0]),
+                "{:?}: {:?}",
+                separator,
+                lines
+            );
+        }
+    }
+
+    #[test]
+    fn test_session_statements() {
+        let sql = r#"/*!40101 SET @OLD_CHARACTER_SET_CLIENT=@@CHARACTER_SET_CLIENT */;
+SET FOREIGN_KEY_CHECKS=0;
+LOCK TABLES `api_keys` WRITE;
+TRUNCATE TABLE api_keys;
+UNLOCK TABLES;"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            ..Default::default()
+        });
+        let expected = r#"/*!40101 SET @OLD_CHARACTER_SET_CLIENT=@@CHARACTER_SET_CLIENT */;
+
+set FOREIGN_KEY_CHECKS = 0
+;
+
+lock tables `api_keys` write
+;
+
+truncate table api_keys
+;
+
+unlock tables
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_wrapped_unique() {
+        let sql = r#"CREATE TABLE operator_settings (col_a int NOT NULL, col_b int NOT NULL, col_c int NOT NULL, col_d int NOT NULL, col_e int NOT NULL, col_f int NOT NULL, col_g int NOT NULL, col_h int NOT NULL, col_i int NOT NULL, col_j int NOT NULL, CONSTRAINT pk_operator_settings PRIMARY KEY (col_a), CONSTRAINT uq_operator_settings_col_a_col_b UNIQUE (col_a, col_b), CONSTRAINT uq_operator_settings_all UNIQUE (col_a, col_b, col_c, col_d, col_e, col_f, col_g, col_h, col_i, col_j) USING BTREE);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            max_width: Some(80),
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE operator_settings (
+    col_a INT NOT NULL
+  , col_b INT NOT NULL
+  , col_c INT NOT NULL
+  , col_d INT NOT NULL
+  , col_e INT NOT NULL
+  , col_f INT NOT NULL
+  , col_g INT NOT NULL
+  , col_h INT NOT NULL
+  , col_i INT NOT NULL
+  , col_j INT NOT NULL
+  , CONSTRAINT pk_operator_settings             PRIMARY KEY (col_a)
+  , CONSTRAINT uq_operator_settings_col_a_col_b UNIQUE      (col_a, col_b)
+  , CONSTRAINT uq_operator_settings_all         UNIQUE      (
+        col_a
+      , col_b
+      , col_c
+      , col_d
+      , col_e
+      , col_f
+      , col_g
+      , col_h
+      , col_i
+      , col_j
+    ) USING BTREE
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_dialect_name() {
+        let sql = r#"CREATE TABLE a (id INT NOT NULL DEFAULT 0);"#;
+
+        for name in AntFarmer::DIALECT_NAMES {
+            let ant_farmer = AntFarmer::try_from_dialect_name(name).unwrap();
+            let expected = "CREATE TABLE a (\n    id INT NOT NULL DEFAULT 0\n)\n;";
+            let result = ant_farmer.format(sql).unwrap();
+
+            assert_eq!(result, expected, "{}", name);
+        }
+
+        assert!(matches!(
+            AntFarmer::try_from_dialect_name("oracle"),
+            Err(AntError::UnknownDialect(_)),
+        ));
+    }
+
+    #[test]
+    fn test_implicit_referential_actions() {
+        let sql = r#"CREATE TABLE a (b_id INT NOT NULL DEFAULT 0, c_id INT NOT NULL DEFAULT 0, CONSTRAINT fk_b FOREIGN KEY (b_id) REFERENCES b (id) ON DELETE RESTRICT ON UPDATE CASCADE, CONSTRAINT fk_c FOREIGN KEY (c_id) REFERENCES c (id) ON DELETE SET NULL ON UPDATE NO ACTION);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            explicit_referential_actions: false,
+            ..Default::default()
+        });
+
+        let expected = r#"CREATE TABLE a (
+    b_id INT NOT NULL DEFAULT 0
+  , c_id INT NOT NULL DEFAULT 0
+  , CONSTRAINT fk_b FOREIGN KEY (b_id) REFERENCES b (id)                    ON UPDATE CASCADE
+  , CONSTRAINT fk_c FOREIGN KEY (c_id) REFERENCES c (id) ON DELETE SET NULL
+)
+;"#;
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_alter_table_lowercase_keywords() {
+        let sql = r#"ALTER TABLE users DROP COLUMN legacy_id, MODIFY COLUMN email VARCHAR(320) NOT NULL AFTER name, CHANGE COLUMN nick nickname VARCHAR(64) NULL FIRST;"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            ..Default::default()
+        });
+        let expected = r#"alter table users
+    drop column legacy_id
+  , modify column email VARCHAR(320) not null after name
+  , change column nick nickname VARCHAR(64) null first
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_lowercase_data_types() {
+        let sql = r#"CREATE TABLE a (id INT(11) UNSIGNED NOT NULL, name VARCHAR(255) NOT NULL, state ENUM('On','Off') NOT NULL, price DECIMAL(10,2) NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            data_type_case: Some(KeywordCase::Lower),
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE a (
+    id    int(11) unsigned  NOT NULL
+  , name  varchar(255)      NOT NULL
+  , state enum('On', 'Off') NOT NULL
+  , price decimal(10,2)         NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_uppercase_data_types() {
+        let sql = r#"CREATE TABLE a (id integer NOT NULL, tags text[] NOT NULL, label "MyType" NULL, at timestamp(6) with time zone NULL);"#;
+        let ant_farmer = AntFarmer::from(PostgreSqlDialect {}).with_options(FormatOptions {
+            data_type_case: Some(KeywordCase::Upper),
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE a (
+    id    INTEGER                     NOT NULL
+  , tags  TEXT[]                      NOT NULL
+  , label "MyType"                        NULL
+  , at    TIMESTAMP(6) WITH TIME ZONE     NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_preserve_data_types() {
+        let sql = r#"CREATE TABLE a (id Int(11) unsigned NOT NULL, tags text[] NOT NULL, price Decimal(10,2) NULL);"#;
+        let ant_farmer = AntFarmer::from(PostgreSqlDialect {}).with_options(FormatOptions {
+            data_type_case: Some(KeywordCase::Preserve),
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE a (
+    id    Int(11) unsigned NOT NULL
+  , tags  text[]           NOT NULL
+  , price Decimal(10,2)        NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_preserve_comments() {
+        let sql = r#"CREATE TABLE a (
+    id INT NOT NULL, -- the key
+    name TEXT NULL,
+    b_id INT NOT NULL /* see b */,
+    CONSTRAINT pk_a PRIMARY KEY (id), -- surrogate
+    CONSTRAINT fk_a_b_id FOREIGN KEY (b_id) REFERENCES b (id) -- cascades elsewhere
+);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            preserve_comments: true,
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE a (
+    id   INT  NOT NULL                                        -- the key
+  , name TEXT     NULL
+  , b_id INT  NOT NULL                                        /* see b */
+  , CONSTRAINT pk_a      PRIMARY KEY (id)                     -- surrogate
+  , CONSTRAINT fk_a_b_id FOREIGN KEY (b_id) REFERENCES b (id) -- cascades elsewhere
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_preserve_comments_trailing_separator() {
+        let sql = r#"CREATE TABLE a (
+    id INT NOT NULL, -- the key
+    CONSTRAINT pk_a PRIMARY KEY (id) -- surrogate
+);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            preserve_comments: true,
+            separator: Separator::Trailing,
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE a (
+    id INT NOT NULL,                 -- the key
+    CONSTRAINT pk_a PRIMARY KEY (id) -- surrogate
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_wrong_dialect_hint() {
+        let sql = r#"CREATE TABLE `a` (id INT(11) NOT NULL);"#;
+        let ant_farmer = AntFarmer::from(PostgreSqlDialect {});
+
+        let result = ant_farmer.format(sql);
+
+        assert!(matches!(result, Err(AntError::MySqlOnly(_))));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .ends_with("(hint: this parses as MySQL; try --dialect mysql)"));
+
+        let sql = r#"CREATE TABLE a (id INT NOT NULL"#;
+
+        assert!(matches!(ant_farmer.format(sql), Err(AntError::Parser(_))));
+    }
+
+    #[test]
+    fn test_comment_on_lowercase_keywords() {
+        let sql = r#"COMMENT ON COLUMN people.name IS 'What they''re called';"#;
+        let ant_farmer = AntFarmer::from(PostgreSqlDialect {})
+            .with_options(FormatOptions::default().with_keyword_case(KeywordCase::Lower));
+        let expected = r#"comment on column people.name is 'What they''re called'
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_global_alignment() {
+        let sql = r#"CREATE TABLE a (id INT NOT NULL, name TEXT NULL); CREATE TABLE bb (id BIGINT NOT NULL, description VARCHAR(255) NOT NULL, PRIMARY KEY (id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {})
+            .with_options(FormatOptions::default().with_global_alignment(true));
+        let expected = r#"CREATE TABLE a (
+    id          INT          NOT NULL
+  , name        TEXT             NULL
+)
+;
+
+CREATE TABLE bb (
+    id          BIGINT       NOT NULL
+  , description VARCHAR(255) NOT NULL
+  , PRIMARY KEY (id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_json_defaults_lowercase() {
+        let sql = r#"CREATE TABLE settings (config JSONB NOT NULL DEFAULT '{}'::jsonb, meta JSONB NULL DEFAULT '{"Key": ["NULL", null]}'::jsonb);"#;
+        let ant_farmer = AntFarmer::from(PostgreSqlDialect {}).with_options(
+            FormatOptions::default()
+                .with_keyword_case(KeywordCase::Lower)
+                .with_data_type_case(Some(KeywordCase::Lower)),
+        );
+        let expected = r#"create table settings (
+    config jsonb not null default '{}'::jsonb
+  , meta   jsonb     null default '{"Key": ["NULL", null]}'::jsonb
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_only_tables() {
+        let sql = r#"CREATE TABLE people (id INT NOT NULL,   name TEXT NULL);
+create table  pets (id INT NOT NULL, name TEXT NULL);
+INSERT INTO pets VALUES (1, 'a;b');"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(
+            FormatOptions::default().with_only_tables(vec!["pets".to_string(), "nope".to_string()]),
+        );
+        let expected = r#"CREATE TABLE people (id INT NOT NULL,   name TEXT NULL);
+
+CREATE TABLE pets (
+    id   INT  NOT NULL
+  , name TEXT     NULL
+)
+;
+
+INSERT INTO pets VALUES (1, 'a;b');"#;
+
+        let (result, warnings) = ant_farmer.format_with_warnings(sql).unwrap();
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            warnings,
+            vec![Warning::UnknownTable {
+                table: "nope".to_string()
+            }],
+        );
+    }
+
+    #[test]
+    fn test_create_table_hoist_inline_keys() {
+        let sql = r#"CREATE TABLE people (id INT NOT NULL PRIMARY KEY, email VARCHAR(320) NOT NULL UNIQUE, team_id INT NULL, CONSTRAINT fk_people_team_id FOREIGN KEY (team_id) REFERENCES teams (id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+        let expected = r#"CREATE TABLE people (
+    id      INT          NOT NULL PRIMARY KEY
+  , email   VARCHAR(320) NOT NULL UNIQUE
+  , team_id INT              NULL
+  , CONSTRAINT fk_people_team_id FOREIGN KEY (team_id) REFERENCES teams (id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+
+        let ant_farmer =
+            ant_farmer.with_options(FormatOptions::default().with_hoist_inline_keys(true));
+        let expected = r#"CREATE TABLE people (
+    id      INT          NOT NULL
+  , email   VARCHAR(320) NOT NULL
+  , team_id INT              NULL
+  , CONSTRAINT fk_people_team_id FOREIGN KEY (team_id) REFERENCES teams (id)
+  ,                              PRIMARY KEY (id)
+  , CONSTRAINT uk_people_email   UNIQUE      (email)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+        assert_eq!(ant_farmer.format(&result).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_create_table_lowercase_binary_types() {
+        let sql = r#"CREATE TABLE files (id BINARY(16) NOT NULL, checksum VARBINARY(255) NULL, body LONGBLOB NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {})
+            .with_options(FormatOptions::default().with_data_type_case(Some(KeywordCase::Lower)));
+        let expected = r#"CREATE TABLE files (
+    id       binary(16)     NOT NULL
+  , checksum varbinary(255)     NULL
+  , body     longblob           NULL
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_foreign_keys_without_actions() {
+        let sql = r#"CREATE TABLE pets (owner_id INT NOT NULL, vet_id INT NULL, CONSTRAINT fk_pets_owner_id FOREIGN KEY (owner_id) REFERENCES people (id) ON DELETE RESTRICT, CONSTRAINT fk_pets_vet_id FOREIGN KEY (vet_id) REFERENCES vets (id) ON UPDATE NO ACTION);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {})
+            .with_options(FormatOptions::default().with_explicit_referential_actions(false));
+        let expected = r#"CREATE TABLE pets (
+    owner_id INT NOT NULL
+  , vet_id   INT     NULL
+  , CONSTRAINT fk_pets_owner_id FOREIGN KEY (owner_id) REFERENCES people (id)
+  , CONSTRAINT fk_pets_vet_id   FOREIGN KEY (vet_id)   REFERENCES vets   (id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_format_text_in_place() {
+        let path = env::temp_dir().join(format!("ant-farmer-text-{}.sql", std::process::id()));
+        fs::write(&path, b"CREATE TABLE caf\xe9 (id INT NOT NULL);").unwrap();
+        let ant_farmer = AntFarmer::from(MySqlDialect {})
+            .with_options(FormatOptions::default().with_only_tables(vec!["tea".to_string()]));
+
+        let (changed, warnings) = ant_farmer
+            .format_text_in_place(&path, "CREATE TABLE caf\u{e9} (id INT NOT NULL);")
+            .unwrap();
+
+        assert!(changed);
+        assert_eq!(
+            warnings,
+            vec![Warning::UnknownTable {
+                table: "tea".to_string()
+            }]
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "CREATE TABLE caf\u{e9} (id INT NOT NULL);\n"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_format_file_in_place() {
+        let path = env::temp_dir().join(format!("ant-farmer-{}.sql", std::process::id()));
+        fs::write(&path, "CREATE TABLE a (id INT NOT NULL);").unwrap();
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+        let expected = r#"CREATE TABLE a (
+    id INT NOT NULL
+)
+;
+"#;
+
+        let changed = ant_farmer.format_file_in_place(&path).unwrap();
+
+        assert!(changed);
+        assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+
+        let changed = ant_farmer.format_file_in_place(&path).unwrap();
+
+        assert!(!changed);
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            ant_farmer.format_file_in_place(&path),
+            Err(AntError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_unterminated_statement() {
+        let sql = r#"CREATE TABLE a (id INT NOT NULL);
+CREATE TABLE b (id INT NOT NULL)"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+        let expected = r#"CREATE TABLE a (
+    id INT NOT NULL
+)
+;
+
+CREATE TABLE b (
+    id INT NOT NULL
+)"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+        assert_eq!(ant_farmer.format(&result).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_statements_with_semicolons_inside() {
+        let sql = r#"CREATE TRIGGER t BEFORE INSERT ON b FOR EACH ROW BEGIN SET NEW.x = 1; SET NEW.y = 2; END;
+CREATE TABLE b (id INT NOT NULL, -- the key
+x INT NULL);
+SELECT 1"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(
+            FormatOptions::default()
+                .with_only_tables(vec!["b".to_string()])
+                .with_preserve_comments(true),
+        );
+        let expected = r#"CREATE TRIGGER t BEFORE INSERT ON b FOR EACH ROW BEGIN SET NEW.x = 1; SET NEW.y = 2; END;
+
+CREATE TABLE b (
+    id INT NOT NULL -- the key
+  , x  INT     NULL
+)
+;
+
+SELECT 1"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_foreign_keys_index_names() {
+        let sql = r#"CREATE TABLE a (b_id INT NOT NULL, c_id INT NOT NULL, FOREIGN KEY ix (b_id) REFERENCES b (id), CONSTRAINT f2 FOREIGN KEY (c_id) REFERENCES c (id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+        let expected = r#"CREATE TABLE a (
+    b_id INT NOT NULL
+  , c_id INT NOT NULL
+  ,               FOREIGN KEY ix (b_id) REFERENCES b (id)
+  , CONSTRAINT f2 FOREIGN KEY    (c_id) REFERENCES c (id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_global_alignment_only_indexes() {
+        let sql = r#"CREATE TABLE a (id INT NOT NULL, KEY ix (id)); CREATE TABLE bb (body TEXT NULL, FULLTEXT KEY ft (body));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {})
+            .with_options(FormatOptions::default().with_global_alignment(true));
+        let expected = r#"CREATE TABLE a (
+    id   INT  NOT NULL
+  , KEY ix (id)
+)
+;
+
+CREATE TABLE bb (
+    body TEXT     NULL
+  , FULLTEXT KEY ft (body)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_table_global_alignment_only_tables() {
+        let sql = r#"CREATE TABLE a (id INT NOT NULL); CREATE TABLE bb (description VARCHAR(255) NOT NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(
+            FormatOptions::default()
+                .with_global_alignment(true)
+                .with_only_tables(vec!["a".to_string()]),
+        );
+        let expected = r#"CREATE TABLE a (
+    id INT NOT NULL
+)
+;
+
+CREATE TABLE bb (description VARCHAR(255) NOT NULL);"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_lowercase_keywords_uppercase_identifiers() {
+        let sql = r#"TRUNCATE TABLE USER; LOCK TABLES STATUS WRITE; ALTER TABLE STATUS ADD COLUMN USER INT NOT NULL DEFAULT 0, ADD KEY TABLES (USER);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {})
+            .with_options(FormatOptions::default().with_keyword_case(KeywordCase::Lower));
+        let expected = r#"truncate table USER
+;
+
+lock tables STATUS write
+;
+
+alter table STATUS
+    add column USER int not null default 0
+  , add key TABLES (USER)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
     }
 
     #[test]
-    fn test_create_table_constraints() {
-        let sql = r#"cReAtE tAbLe operators_create_consumers (operator_api_key_id    int(11)    NOT NULL, operator_ip_address_id int(11)   nOt NuLl, create_consumers JSON NuLl, created_date datetime nOt NuLl dEfAuLt CURRENT_TIMESTAMP() , CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id ) REFERENCES api_keys (id) , CONSTRAINT fk_operators_create_consumers_operator_ip_address_id  FOREIGN KEY (operator_ip_address_id ) REFERENCES operator_ip_addresses (id) , CONSTRAINT uq_operator_api_key_id_operator_ip_address_id UNIQUE (operator_api_key_id, operator_ip_address_id));"#;
+    fn test_format_iter() {
+        let sql = r#"CREATE SCHEMA a; CREATE TABLE a.b (id INT NOT NULL DEFAULT 0); TRUNCATE a.b;"#;
         let ant_farmer = AntFarmer::from(MySqlDialect {});
+        let expected = vec![
+            "CREATE SCHEMA a\n;",
+            "CREATE TABLE a.b (\n    id INT NOT NULL DEFAULT 0\n)\n;",
+            "TRUNCATE a.b\n;",
+        ];
+
+        let result = ant_farmer
+            .format_iter(sql)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(result, expected);
+
+        let mut result = ant_farmer.format_iter("CREATE TABLE (");
+        assert!(matches!(result.next(), Some(Err(AntError::Parser(_)))));
+        assert!(result.next().is_none());
+    }
+
+    #[test]
+    fn test_insert_aligned_values() {
+        let sql = r#"INSERT INTO currencies (id, code, name, rate) VALUES (1, 'GBP', 'Pound sterling', 1), (20, 'EUR', 'Euro', 1.17), (300, 'JPY', NULL, -188.5);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            align_insert_values: true,
+            ..Default::default()
+        });
+        let expected = r#"INSERT INTO currencies (id, code, name, rate) VALUES
+    (  1, 'GBP', 'Pound sterling',      1)
+  , ( 20, 'EUR', 'Euro',             1.17)
+  , (300, 'JPY', NULL,             -188.5)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let sql = r#"CREATE TABLE a (
+    id INT UNSIGNED NOT NULL AUTO_INCREMENT, -- the key
+    b_id INT NULL,
+    total DECIMAL(10,2) GENERATED ALWAYS AS (price * quantity) STORED NOT NULL,
+    updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    CONSTRAINT pk_a PRIMARY KEY (id) USING BTREE,
+    CONSTRAINT fk_a_b_id FOREIGN KEY (b_id) REFERENCES b (id) ON DELETE SET NULL ON UPDATE RESTRICT,
+    UNIQUE KEY uq_a (b_id, total)
+) COLLATE=utf8mb4_unicode_ci ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 AUTO_INCREMENT=5;
+ALTER TABLE a DROP COLUMN b_id, MODIFY COLUMN total INT NOT NULL;
+INSERT INTO a (id, b_id) VALUES (1, NULL), (20, 3);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            preserve_comments: true,
+            align_insert_values: true,
+            max_width: Some(40),
+            ..Default::default()
+        });
+
+        let expected = ant_farmer.format(sql).unwrap();
+        for _ in 0..100 {
+            assert_eq!(ant_farmer.format(sql).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_create_table_compact() {
+        let sql = r#"CREATE TABLE operators_create_consumers (operator_api_key_id int(11) NOT NULL, create_consumers JSON NULL, created_date datetime NOT NULL DEFAULT CURRENT_TIMESTAMP(), CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id) REFERENCES api_keys (id), CONSTRAINT uq_operator_api_key_id UNIQUE (operator_api_key_id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            compact: true,
+            ..Default::default()
+        });
         let expected = r#"CREATE TABLE operators_create_consumers (
-    operator_api_key_id    INT(11)  NOT NULL                            
-  , operator_ip_address_id INT(11)  NOT NULL                            
-  , create_consumers       JSON         NULL                            
-  , created_date           datetime NOT NULL DEFAULT CURRENT_TIMESTAMP()
-  , CONSTRAINT fk_operators_create_consumers_operator_api_key_id    FOREIGN KEY (operator_api_key_id)                         REFERENCES api_keys              (id)
-  , CONSTRAINT fk_operators_create_consumers_operator_ip_address_id FOREIGN KEY (operator_ip_address_id)                      REFERENCES operator_ip_addresses (id)
-  , CONSTRAINT uq_operator_api_key_id_operator_ip_address_id        UNIQUE      (operator_api_key_id, operator_ip_address_id)
+    operator_api_key_id INT(11) NOT NULL
+  , create_consumers JSON NULL
+  , created_date DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP()
+  , CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id) REFERENCES api_keys (id)
+  , CONSTRAINT uq_operator_api_key_id UNIQUE (operator_api_key_id)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_unknown_foreign_key_column() {
+        let sql = r#"CREATE TABLE a (b_id INT NOT NULL, CONSTRAINT fk_a_b_id FOREIGN KEY (b_idd) REFERENCES b (id), FOREIGN KEY (B_ID) REFERENCES b (id));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+
+        let (result, warnings) = ant_farmer.format_with_warnings(sql).unwrap();
+
+        assert_eq!(result, ant_farmer.format(sql).unwrap());
+        assert_eq!(
+            warnings,
+            vec![Warning::UnknownForeignKeyColumn {
+                table: "a".to_string(),
+                constraint: Some("fk_a_b_id".to_string()),
+                column: "b_idd".to_string(),
+            }],
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "a: foreign key fk_a_b_id refers to unknown column b_idd",
+        );
+    }
+
+    #[test]
+    fn test_create_table_max_column_contribution() {
+        let sql = r#"CREATE TABLE bookings (id INT NOT NULL, room_id INT NOT NULL, guest_id INT NOT NULL, start_date DATE NOT NULL, end_date DATE NOT NULL, CONSTRAINT fk_bookings_room_id FOREIGN KEY (room_id) REFERENCES rooms (id), CONSTRAINT fk_bookings_guest_id FOREIGN KEY (guest_id) REFERENCES guests (id), CONSTRAINT ck_bookings_dates CHECK (start_date < end_date AND end_date < start_date + INTERVAL 1 YEAR));"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(FormatOptions {
+            max_column_contribution: Some(40),
+            ..Default::default()
+        });
+        let expected = r#"CREATE TABLE bookings (
+    id         INT  NOT NULL
+  , room_id    INT  NOT NULL
+  , guest_id   INT  NOT NULL
+  , start_date DATE NOT NULL
+  , end_date   DATE NOT NULL
+  , CONSTRAINT fk_bookings_room_id  FOREIGN KEY (room_id)  REFERENCES rooms  (id)
+  , CONSTRAINT fk_bookings_guest_id FOREIGN KEY (guest_id) REFERENCES guests (id)
+  , CONSTRAINT ck_bookings_dates    CHECK (start_date < end_date AND end_date < start_date + INTERVAL 1 YEAR)
+)
+;"#;
+
+        let result = ant_farmer.format(sql).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_format_with_ast() {
+        let sql = r#"CREATE SCHEMA a; CREATE TABLE a.b (id INT NOT NULL DEFAULT 0);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {});
+
+        let (result, ast) = ant_farmer.format_with_ast(sql).unwrap();
+
+        assert_eq!(result, ant_farmer.format(sql).unwrap());
+        assert_eq!(ast, Parser::parse_sql(&MySqlDialect {}, sql).unwrap());
+    }
+
+    #[test]
+    fn test_create_table_trailing_comma_last_item() {
+        let sql = r#"CREATE TABLE a (id INT64 NOT NULL, name STRING);"#;
+        let ant_farmer = AntFarmer::from(BigQueryDialect {}).with_options(
+            FormatOptions::default()
+                .with_separator(Separator::Trailing)
+                .with_trailing_comma_last_item(true),
+        );
+        let expected = r#"CREATE TABLE a (
+    id   INT64  NOT NULL,
+    name STRING,
+)
+;"#;
+
+        let (result, warnings) = ant_farmer.format_with_warnings(sql).unwrap();
+
+        assert_eq!(result, expected);
+        assert_eq!(warnings, vec![]);
+        assert_eq!(ant_farmer.format(&result).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_create_table_trailing_comma_last_item_unsupported() {
+        let sql = r#"CREATE TABLE a (id INT NOT NULL, name TEXT NULL);"#;
+        let ant_farmer = AntFarmer::from(MySqlDialect {}).with_options(
+            FormatOptions::default()
+                .with_separator(Separator::Trailing)
+                .with_trailing_comma_last_item(true),
+        );
+        let expected = r#"CREATE TABLE a (
+    id   INT  NOT NULL,
+    name TEXT     NULL
 )
 ;"#;
 
-        let result = ant_farmer.mierenneuke(sql).unwrap();
+        let (result, warnings) = ant_farmer.format_with_warnings(sql).unwrap();
 
         assert_eq!(result, expected);
+        assert_eq!(
+            warnings,
+            vec![Warning::TrailingCommaIgnored {
+                table: "a".to_string()
+            }],
+        );
     }
 }
@@ -1,11 +1,756 @@
-use ant_farmer::AntFarmer;
-use sqlparser::dialect::MySqlDialect;
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+    thread,
+    time::Instant,
+};
 
-#[allow(unused_variables)]
-fn main() {
-    let sql = r#"cReAtE tAbLe operators_create_consumers (operator_api_key_id    int(11)    NOT NULL, operator_ip_address_id int(11)   nOt NuLl, create_consumers JSON NuLl, created_date datetime nOt NuLl dEfAuLt CURRENT_TIMESTAMP() , CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id ) REFERENCES api_keys (id) , CONSTRAINT fk_operators_create_consumers_operator_ip_address_id  FOREIGN KEY (operator_ip_address_id ) REFERENCES operator_ip_addresses (id), CONSTRAINT uq_operator_api_key_id_operator_ip_address_id UNIQUE (operator_api_key_id, operator_ip_address_id));"#;
+use ant_farmer::{AntError, AntFarmer, FormatOptions, Warning};
+use log::{debug, info, LevelFilter, Log, Metadata, Record};
 
-    let ant_farmer = AntFarmer::from(MySqlDialect {});
+const USAGE: &str = "Usage: ant-farmer [--check | --diff | --write] [--dialect <DIALECT>] [--table <TABLE>]... [--parallel] [--verbose] [--encoding <ENCODING>] [--stdin-filename <PATH>] [FILE]...
+       ant-farmer --list-dialects
 
-    println!("{}", ant_farmer.mierenneuke(sql).unwrap());
+Formats each FILE (or standard input, if none are given or FILE is -) in the house style.
+
+Options:
+      --check                  Report files which would be reformatted; exit 1 if any
+      --diff                   Print a diff of the changes which would be made; exit 1 if any
+      --write                  Rewrite each FILE in place (always as UTF-8)
+      --dialect <DIALECT>      The SQL dialect: mysql (the default), postgres, sqlite, mssql or generic
+      --table <TABLE>          Format only this table's CREATE TABLE, leaving everything else as written;
+                               may be given more than once
+      --parallel               Format the FILEs on several threads; output stays in order
+  -v, --verbose                Report each file's progress and timing on standard error, as does
+                               RUST_LOG=ant_farmer=info (or any other RUST_LOG directives)
+      --encoding <ENCODING>    The encoding of the input: utf-8 (the default) or latin1
+      --stdin-filename <PATH>  The path to show for standard input in diagnostics and diffs
+      --list-dialects          Print the names accepted by --dialect
+  -h, --help                   Print this help";
+
+/// What to do with the formatted output.
+#[derive(PartialEq)]
+enum Mode {
+    Print,
+    Check,
+    Diff,
+    Write,
+}
+
+/// The encodings we know how to transcode to UTF-8 before formatting.
+#[derive(Clone, Copy)]
+enum Encoding {
+    Utf8,
+    Latin1,
+}
+
+impl Encoding {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" => Some(Self::Latin1),
+            _ => None,
+        }
+    }
+
+    /// Decodes `bytes` read from `path`, dropping any byte order mark.
+    fn decode(self, path: &str, bytes: Vec<u8>) -> Result<String, String> {
+        let text = match self {
+            Self::Utf8 => String::from_utf8(bytes).map_err(|err| {
+                format!(
+                    "{}: invalid UTF-8 at byte offset {}; if this is a Latin-1 file, try --encoding latin1",
+                    path,
+                    err.utf8_error().valid_up_to(),
+                )
+            })?,
+            // Every Latin-1 byte is the Unicode code point of the same value.
+            Self::Latin1 => bytes.into_iter().map(char::from).collect(),
+        };
+
+        Ok(match text.strip_prefix('\u{feff}') {
+            Some(text) => text.to_string(),
+            None => text,
+        })
+    }
+}
+
+/// Writes log records to standard error, filtered by `RUST_LOG`-style
+/// directives: a comma-separated list of `target=level`, `target` (for every
+/// level) or just `level` (for every target), the longest matching target
+/// winning, e.g. `warn,ant_farmer=info`.
+struct Logger {
+    directives: Vec<(Option<String>, LevelFilter)>,
+}
+
+impl Logger {
+    fn parse(directives: &str) -> Self {
+        let directives = directives
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .filter_map(|directive| match directive.split_once('=') {
+                Some((target, level)) => Some((Some(target.to_string()), level.parse().ok()?)),
+                None => Some(match directive.parse() {
+                    Ok(level) => (None, level),
+                    Err(_) => (Some(directive.to_string()), LevelFilter::Trace),
+                }),
+            })
+            .collect();
+
+        Self { directives }
+    }
+
+    /// The most verbose level wanted for `target`.
+    fn level(&self, target: &str) -> LevelFilter {
+        self.directives
+            .iter()
+            .filter(|(wanted, _)| {
+                wanted.as_ref().is_none_or(|wanted| {
+                    target == wanted || target.starts_with(&format!("{}::", wanted))
+                })
+            })
+            .max_by_key(|(wanted, _)| wanted.as_ref().map_or(0, |wanted| wanted.len() + 1))
+            .map_or(LevelFilter::Off, |(_, level)| *level)
+    }
+
+    /// Logs as per `RUST_LOG`, if set; `verbose` asks for this crate's
+    /// progress too.
+    fn init(verbose: bool) {
+        let mut logger = Self::parse(&env::var("RUST_LOG").unwrap_or_default());
+        if verbose && logger.level(env!("CARGO_CRATE_NAME")) < LevelFilter::Info {
+            logger.directives.push((
+                Some(env!("CARGO_CRATE_NAME").to_string()),
+                LevelFilter::Info,
+            ));
+        }
+
+        let max_level = logger
+            .directives
+            .iter()
+            .map(|(_, level)| *level)
+            .max()
+            .unwrap_or(LevelFilter::Off);
+        if log::set_boxed_logger(Box::new(logger)).is_ok() {
+            log::set_max_level(max_level);
+        }
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "{:>5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+struct Args {
+    mode: Mode,
+    dialect: String,
+    list_dialects: bool,
+    tables: Vec<String>,
+    parallel: bool,
+    verbose: bool,
+    encoding: Encoding,
+    stdin_filename: Option<String>,
+    files: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut mode = Mode::Print;
+    let mut dialect = "mysql".to_string();
+    let mut list_dialects = false;
+    let mut tables = vec![];
+    let mut parallel = false;
+    let mut verbose = false;
+    let mut encoding = Encoding::Utf8;
+    let mut stdin_filename = None;
+    let mut files = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => mode = Mode::Check,
+            "--diff" => mode = Mode::Diff,
+            "--write" => mode = Mode::Write,
+            "--dialect" => dialect = args.next().ok_or("--dialect needs a dialect")?,
+            "--table" => tables.push(args.next().ok_or("--table needs a table name")?),
+            "--encoding" => {
+                let name = args.next().ok_or("--encoding needs an encoding")?;
+                encoding = Encoding::from_name(&name)
+                    .ok_or_else(|| format!("unknown encoding: {}", name))?;
+            }
+            "--stdin-filename" => {
+                stdin_filename = Some(args.next().ok_or("--stdin-filename needs a path")?);
+            }
+            "--list-dialects" => list_dialects = true,
+            "--parallel" => parallel = true,
+            "-v" | "--verbose" => verbose = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown option: {}\n\n{}", flag, USAGE));
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    if mode == Mode::Write && files.is_empty() {
+        return Err("--write needs at least one FILE".to_string());
+    }
+    if mode == Mode::Write && files.iter().any(|file| file == "-") {
+        return Err("--write can't rewrite standard input".to_string());
+    }
+
+    Ok(Args {
+        mode,
+        dialect,
+        list_dialects,
+        tables,
+        parallel,
+        verbose,
+        encoding,
+        stdin_filename,
+        files,
+    })
+}
+
+/// A minimal unified diff of `before` and `after`, as a single hunk.
+///
+/// Lines are compared along with their line endings, so that a missing final
+/// newline counts as a change, marked as `diff` would.
+fn diff(path: &str, before: &str, after: &str) -> String {
+    let before = before.split_inclusive('\n').collect::<Vec<_>>();
+    let after = after.split_inclusive('\n').collect::<Vec<_>>();
+    // An empty range starts at line 0.
+    let range = |lines: &[&str]| format!("{},{}", usize::from(!lines.is_empty()), lines.len());
+
+    let mut output = format!(
+        "--- {path}\n+++ {path}\n@@ -{} +{} @@\n",
+        range(&before),
+        range(&after),
+    );
+    let mut edits = vec![];
+    edit_script(&before, &after, &mut edits);
+    // Within each run of changes, what's removed reads better first.
+    for changes in edits.chunk_by_mut(|a, b| (a.0 == ' ') == (b.0 == ' ')) {
+        changes.sort_by_key(|(tag, _)| *tag == '+');
+    }
+    for (tag, line) in edits {
+        output.push(tag);
+        output += line;
+        if !line.ends_with('\n') {
+            output += "\n\\ No newline at end of file\n";
+        }
+    }
+
+    output
+}
+
+/// Appends the shortest edit script turning `before` into `after` to `edits`:
+/// each line is tagged ` ` if kept, `-` if removed or `+` if added.
+///
+/// Lines in common at either end are set aside first; what's left is split at
+/// its middle snake, as per Myers' "An O(ND) Difference Algorithm and Its
+/// Variations", so that only linear space is needed however big the files.
+fn edit_script<'a>(before: &[&'a str], after: &[&'a str], edits: &mut Vec<(char, &'a str)>) {
+    let prefix = before
+        .iter()
+        .zip(after)
+        .take_while(|(before, after)| before == after)
+        .count();
+    edits.extend(before[..prefix].iter().map(|line| (' ', *line)));
+    let (before, after) = (&before[prefix..], &after[prefix..]);
+
+    let suffix = before
+        .iter()
+        .rev()
+        .zip(after.iter().rev())
+        .take_while(|(before, after)| before == after)
+        .count();
+    let common = &before[before.len() - suffix..];
+    let (before, after) = (
+        &before[..before.len() - suffix],
+        &after[..after.len() - suffix],
+    );
+
+    if before.is_empty() {
+        edits.extend(after.iter().map(|line| ('+', *line)));
+    } else if after.is_empty() {
+        edits.extend(before.iter().map(|line| ('-', *line)));
+    } else {
+        // Both ends differ, so there are at least two edits and either side
+        // of the snake needs fewer.
+        let (x, y, u, v) = middle_snake(before, after);
+        edit_script(&before[..x], &after[..y], edits);
+        edits.extend(before[x..u].iter().map(|line| (' ', *line)));
+        edit_script(&before[u..], &after[v..], edits);
+    }
+
+    edits.extend(common.iter().map(|line| (' ', *line)));
+}
+
+/// The start and end, `(x, y)` and `(u, v)`, of the snake halfway along a
+/// shortest edit script turning `a` into `b`, found by searching forwards from
+/// the start and backwards from the end at once until the two meet.
+fn middle_snake(a: &[&str], b: &[&str]) -> (usize, usize, usize, usize) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let max = (n + m + 1) / 2;
+    let index = |k: isize| (k + max + 1) as usize;
+
+    // The furthest `x` reached along each diagonal `k = x - y`; backwards,
+    // both are counted from the ends.
+    let mut forward = vec![0; index(max + 1) + 1];
+    let mut backward = vec![0; index(max + 1) + 1];
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[index(k - 1)] < forward[index(k + 1)]) {
+                forward[index(k + 1)]
+            } else {
+                forward[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[index(k)] = x;
+
+            let c = delta - k;
+            if delta % 2 != 0 && (1 - d..d).contains(&c) && x + backward[index(c)] >= n {
+                return (x0 as usize, y0 as usize, x as usize, y as usize);
+            }
+        }
+
+        for c in (-d..=d).step_by(2) {
+            let mut x = if c == -d || (c != d && backward[index(c - 1)] < backward[index(c + 1)]) {
+                backward[index(c + 1)]
+            } else {
+                backward[index(c - 1)] + 1
+            };
+            let mut y = x - c;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[index(c)] = x;
+
+            let k = delta - c;
+            if delta % 2 == 0 && (-d..=d).contains(&k) && x + forward[index(k)] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x0) as usize,
+                    (m - y0) as usize,
+                );
+            }
+        }
+    }
+
+    unreachable!("the searches meet by half the combined length")
+}
+
+/// What formatting a file came to.
+struct Outcome {
+    /// Whether formatting changes the file.
+    changed: bool,
+    /// The formatted contents, unless they've been written back already.
+    formatted: Option<String>,
+    warnings: Vec<Warning>,
+}
+
+/// Formats each of `sources` in turn, rewriting those which change if `write`.
+fn format_all(
+    dialect: &str,
+    options: &FormatOptions,
+    write: bool,
+    sources: &[(String, String)],
+) -> Vec<Result<Outcome, AntError>> {
+    let mut ant_farmer = AntFarmer::try_from_dialect_name(dialect).expect("checked in main");
+    ant_farmer.set_options(options.clone());
+
+    sources
+        .iter()
+        .map(|(path, sql)| {
+            debug!("formatting {}", path);
+            let start = Instant::now();
+            let outcome = if write {
+                ant_farmer
+                    .format_text_in_place(path, sql)
+                    .map(|(changed, warnings)| Outcome {
+                        changed,
+                        formatted: None,
+                        warnings,
+                    })
+            } else {
+                ant_farmer
+                    .format_with_warnings(sql)
+                    .map(|(formatted, warnings)| {
+                        let formatted = formatted + "\n";
+                        Outcome {
+                            changed: formatted != *sql,
+                            formatted: Some(formatted),
+                            warnings,
+                        }
+                    })
+            };
+            // Logged as each file is done, for some sign of progress through
+            // a large batch.
+            let elapsed = start.elapsed();
+            match &outcome {
+                Ok(Outcome { changed: true, .. }) => {
+                    info!("{}: reformatted in {:.1?}", path, elapsed)
+                }
+                Ok(Outcome { changed: false, .. }) => {
+                    info!("{}: unchanged in {:.1?}", path, elapsed)
+                }
+                Err(err) => info!("{}: failed in {:.1?}: {}", path, elapsed, err),
+            }
+            outcome
+        })
+        .collect()
+}
+
+/// As [`format_all`], but spread over a thread per CPU; the results are in the
+/// same order as `sources`.
+fn format_parallel(
+    dialect: &str,
+    options: &FormatOptions,
+    write: bool,
+    sources: &[(String, String)],
+) -> Vec<Result<Outcome, AntError>> {
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    let chunk_size = sources.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        sources
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || format_all(dialect, options, write, chunk)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("formatting thread panicked"))
+            .collect()
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    Logger::init(args.verbose);
+
+    if args.list_dialects {
+        for name in AntFarmer::DIALECT_NAMES {
+            println!("{}", name);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if let Err(err) = AntFarmer::try_from_dialect_name(&args.dialect) {
+        eprintln!("{}", err);
+        return ExitCode::from(2);
+    }
+
+    // A file that can't be read, decoded or formatted is reported, and the
+    // rest carry on regardless: with `--write`, some may be rewritten already.
+    let mut failed = false;
+    let files = if args.files.is_empty() {
+        vec!["-".to_string()]
+    } else {
+        args.files
+    };
+    let mut inputs = vec![];
+    for file in files {
+        let (path, read) = if file == "-" {
+            let path = args
+                .stdin_filename
+                .clone()
+                .unwrap_or_else(|| "<stdin>".to_string());
+            let mut bytes = vec![];
+            (path, io::stdin().read_to_end(&mut bytes).map(|_| bytes))
+        } else {
+            let read = fs::read(&file);
+            (file, read)
+        };
+        match read {
+            Ok(bytes) => inputs.push((path, bytes)),
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                failed = true;
+            }
+        }
+    }
+
+    let mut sources = vec![];
+    for (path, bytes) in inputs {
+        match args.encoding.decode(&path, bytes) {
+            Ok(sql) => sources.push((path, sql)),
+            Err(message) => {
+                eprintln!("{}", message);
+                failed = true;
+            }
+        }
+    }
+
+    let options = FormatOptions::default().with_only_tables(args.tables);
+    let started = Instant::now();
+    let write = args.mode == Mode::Write;
+    let results = if args.parallel {
+        format_parallel(&args.dialect, &options, write, &sources)
+    } else {
+        format_all(&args.dialect, &options, write, &sources)
+    };
+
+    let mut changed = false;
+    let mut reformatted = 0;
+    for ((path, sql), result) in sources.iter().zip(results) {
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                failed = true;
+                continue;
+            }
+        };
+        for warning in outcome.warnings {
+            eprintln!("{}: warning: {}", path, warning);
+        }
+
+        if outcome.changed {
+            reformatted += 1;
+        }
+
+        // With `--write`, the file has been rewritten already.
+        let Some(formatted) = outcome.formatted else {
+            continue;
+        };
+        match args.mode {
+            Mode::Print => print!("{}", formatted),
+            Mode::Check => {
+                if outcome.changed {
+                    println!("Would reformat: {}", path);
+                    changed = true;
+                }
+            }
+            Mode::Diff => {
+                if outcome.changed {
+                    print!("{}", diff(path, sql, &formatted));
+                    changed = true;
+                }
+            }
+            Mode::Write => {}
+        }
+    }
+
+    info!(
+        "{} file(s), {} reformatted, in {:.1?}",
+        sources.len(),
+        reformatted,
+        started.elapsed(),
+    );
+
+    if failed {
+        ExitCode::from(2)
+    } else if changed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = args(&[
+            "--diff",
+            "--dialect",
+            "postgres",
+            "--table",
+            "a",
+            "--table",
+            "b",
+            "--encoding",
+            "latin1",
+            "--parallel",
+            "one.sql",
+            "-",
+        ])
+        .unwrap();
+
+        assert!(args.mode == Mode::Diff);
+        assert_eq!(args.dialect, "postgres");
+        assert_eq!(args.tables, vec!["a", "b"]);
+        assert!(matches!(args.encoding, Encoding::Latin1));
+        assert!(args.parallel);
+        assert!(!args.list_dialects);
+        assert_eq!(args.stdin_filename, None);
+        assert_eq!(args.files, vec!["one.sql", "-"]);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        let error = |arguments: &[&str]| args(arguments).err().unwrap();
+
+        assert!(error(&["--nope"]).starts_with("unknown option: --nope\n"));
+        assert_eq!(error(&["--dialect"]), "--dialect needs a dialect");
+        assert_eq!(error(&["--encoding", "ebcdic"]), "unknown encoding: ebcdic");
+        assert_eq!(error(&["--write"]), "--write needs at least one FILE");
+        assert_eq!(
+            error(&["--write", "a.sql", "-"]),
+            "--write can't rewrite standard input"
+        );
+    }
+
+    #[test]
+    fn test_logger_level() {
+        let logger = Logger::parse("warn, ant_farmer=info,ant_farmer::deep=trace,sqlparser=nope");
+
+        assert_eq!(logger.level("ant_farmer"), LevelFilter::Info);
+        assert_eq!(logger.level("ant_farmer::deep::er"), LevelFilter::Trace);
+        assert_eq!(logger.level("ant_farmers"), LevelFilter::Warn);
+        assert_eq!(logger.level("sqlparser::parser"), LevelFilter::Warn);
+
+        let logger = Logger::parse("ant_farmer");
+
+        assert_eq!(logger.level("ant_farmer"), LevelFilter::Trace);
+        assert_eq!(logger.level("sqlparser"), LevelFilter::Off);
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = "CREATE TABLE a (\n  id INT\n)\n;\nDROP TABLE b;\n";
+        let after = "CREATE TABLE a (\n    id INT\n)\n;\n";
+        let expected = "--- a.sql
++++ a.sql
+@@ -1,5 +1,4 @@
+ CREATE TABLE a (
+-  id INT
++    id INT
+ )
+ ;
+-DROP TABLE b;
+";
+
+        assert_eq!(diff("a.sql", before, after), expected);
+    }
+
+    #[test]
+    fn test_diff_final_newline() {
+        let expected = "--- a.sql
++++ a.sql
+@@ -1,2 +1,2 @@
+ SELECT 1
+-;
+\\ No newline at end of file
++;
+";
+
+        assert_eq!(diff("a.sql", "SELECT 1\n;", "SELECT 1\n;\n"), expected);
+    }
+
+    #[test]
+    fn test_diff_empty() {
+        let expected = "--- a.sql
++++ a.sql
+@@ -0,0 +1,1 @@
++SELECT 1;
+";
+
+        assert_eq!(diff("a.sql", "", "SELECT 1;\n"), expected);
+    }
+
+    #[test]
+    fn test_edit_script_is_shortest() {
+        // The longest common subsequence, the slow way.
+        fn common(a: &[&str], b: &[&str]) -> usize {
+            let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+            for i in (0..a.len()).rev() {
+                for j in (0..b.len()).rev() {
+                    lengths[i][j] = if a[i] == b[j] {
+                        lengths[i + 1][j + 1] + 1
+                    } else {
+                        lengths[i + 1][j].max(lengths[i][j + 1])
+                    };
+                }
+            }
+            lengths[0][0]
+        }
+
+        let lines = ["a", "b", "c", "d"];
+        let mut seed = 1u32;
+        let mut next = |len: usize| {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    lines[(seed >> 16) as usize % lines.len()]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for len in 0..40 {
+            let (before, after) = (next(len), next(40 - len));
+            let mut edits = vec![];
+            edit_script(&before, &after, &mut edits);
+
+            let side = |tag| {
+                edits
+                    .iter()
+                    .filter(|(t, _)| *t == ' ' || *t == tag)
+                    .map(|(_, line)| *line)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(side('-'), before);
+            assert_eq!(side('+'), after);
+
+            let kept = edits.iter().filter(|(tag, _)| *tag == ' ').count();
+            assert_eq!(kept, common(&before, &after));
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let decoded = Encoding::Latin1.decode("a.sql", b"caf\xe9".to_vec());
+        assert_eq!(decoded.unwrap(), "caf\u{e9}");
+
+        let decoded = Encoding::Utf8.decode("a.sql", b"\xef\xbb\xbfSELECT 1;".to_vec());
+        assert_eq!(decoded.unwrap(), "SELECT 1;");
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        let error = Encoding::Utf8
+            .decode("a.sql", b"SELECT 'caf\xe9';".to_vec())
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            "a.sql: invalid UTF-8 at byte offset 11; if this is a Latin-1 file, try --encoding latin1"
+        );
+    }
 }
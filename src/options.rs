@@ -0,0 +1,170 @@
+/// How the keywords we emit ourselves (`CREATE TABLE`, `NOT NULL`,
+/// `FOREIGN KEY`, …) are cased.
+///
+/// Expressions and identifiers are left alone, as are data types unless
+/// [`FormatOptions::data_type_case`] says otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeywordCase {
+    /// `CREATE TABLE`
+    #[default]
+    Upper,
+    /// `create table`
+    Lower,
+    /// Whatever the source used, e.g. `cReAtE tAbLe`.
+    ///
+    /// Where a keyword appears several times in a statement, the casing of its
+    /// first occurrence wins.
+    Preserve,
+}
+
+/// What goes between one column (or constraint) and the next.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Separator {
+    /// A leading comma on each new line:
+    ///
+    /// ```sql
+    ///     id   INT
+    ///   , name TEXT
+    /// ```
+    #[default]
+    Leading,
+    /// A trailing comma at the end of each line:
+    ///
+    /// ```sql
+    ///     id   INT,
+    ///     name TEXT
+    /// ```
+    Trailing,
+    /// Any other string; it must contain a newline so that the output stays
+    /// one column per line.
+    Custom(String),
+}
+
+impl Separator {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Separator::Leading => "\n  , ",
+            Separator::Trailing => ",\n    ",
+            Separator::Custom(separator) => separator,
+        }
+    }
+}
+
+/// The knobs of the "house style".
+///
+/// The default matches the style the formatter has always produced: leading
+/// commas, four-space indent, uppercase keywords and right-aligned
+/// nullability. New knobs may be added at any time, so build on the default:
+///
+/// ```
+/// use ant_farmer::{FormatOptions, KeywordCase, Separator};
+///
+/// let options = FormatOptions::default()
+///     .with_keyword_case(KeywordCase::Lower)
+///     .with_separator(Separator::Trailing);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FormatOptions {
+    /// How to case the keywords we emit.
+    pub keyword_case: KeywordCase,
+    /// Emit column options in the order they were written rather than the
+    /// canonical `NOT NULL DEFAULT …`, for byte-faithful round trips.
+    pub preserve_option_order: bool,
+    /// See [`Separator`].
+    pub separator: Separator,
+    /// The width beyond which a `PRIMARY KEY`/`UNIQUE` constraint's column
+    /// list is wrapped, one column per line; `None` never wraps.
+    pub max_width: Option<usize>,
+    /// The longest a segment (a data type, a `CHECK`, a default, …) can be
+    /// and still count towards the width of its column. Anything longer
+    /// overflows, pushing the rest of its own line along rather than widening
+    /// every line; `None` never caps.
+    pub max_column_contribution: Option<usize>,
+    /// Emit foreign keys' `ON DELETE`/`ON UPDATE` actions as parsed; when
+    /// `false`, the engines' implicit `RESTRICT` and `NO ACTION` are omitted.
+    pub explicit_referential_actions: bool,
+    /// How to case the names of data types, e.g. `varchar(255)`; `None`
+    /// leaves them as the parser renders them.
+    pub data_type_case: Option<KeywordCase>,
+    /// Keep the comments following a `CREATE TABLE`'s columns and
+    /// constraints, lined up after the longest line.
+    pub preserve_comments: bool,
+    /// Put each row of an `INSERT … VALUES` on a line of its own, with the
+    /// values lined up in columns. Handy for seed data, though a single wide
+    /// value widens every row.
+    pub align_insert_values: bool,
+    /// Skip the alignment altogether: still one column (or constraint) per
+    /// line, but with a single space between each part and no padding.
+    pub compact: bool,
+    /// With [`Separator::Trailing`] (or a custom separator ending its lines
+    /// with a comma), put a comma after the last column or constraint too, so
+    /// that adding another is a one-line diff. Only dialects which parse that
+    /// get one; see [`Warning::TrailingCommaIgnored`](crate::Warning::TrailingCommaIgnored).
+    pub trailing_comma_last_item: bool,
+    /// Line up the columns (and constraints) of every `CREATE TABLE` in the
+    /// input with each other, rather than each table's with its own, for a
+    /// file of many similar tables.
+    pub global_alignment: bool,
+    /// Format only the `CREATE TABLE`s of these tables, passing every other
+    /// statement through as written; empty formats everything. See
+    /// [`Warning::UnknownTable`](crate::Warning::UnknownTable).
+    pub only_tables: Vec<String>,
+    /// Move the `PRIMARY KEY` and `UNIQUE` declared on columns themselves
+    /// into constraints after the rest, so that every key is listed together.
+    pub hoist_inline_keys: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            keyword_case: KeywordCase::default(),
+            preserve_option_order: false,
+            separator: Separator::default(),
+            max_width: None,
+            max_column_contribution: None,
+            explicit_referential_actions: true,
+            data_type_case: None,
+            preserve_comments: false,
+            align_insert_values: false,
+            compact: false,
+            trailing_comma_last_item: false,
+            global_alignment: false,
+            only_tables: vec![],
+            hoist_inline_keys: false,
+        }
+    }
+}
+
+/// A `with_<field>` setter for each field, so that options can be built up
+/// from the default in a single expression.
+macro_rules! setters {
+    ($($field:ident: $type:ty => $setter:ident),* $(,)?) => {
+        impl FormatOptions {
+            $(
+                #[doc = concat!("Sets [`FormatOptions::", stringify!($field), "`].")]
+                pub fn $setter(mut self, $field: $type) -> Self {
+                    self.$field = $field;
+                    self
+                }
+            )*
+        }
+    };
+}
+
+setters! {
+    keyword_case: KeywordCase => with_keyword_case,
+    preserve_option_order: bool => with_preserve_option_order,
+    separator: Separator => with_separator,
+    max_width: Option<usize> => with_max_width,
+    max_column_contribution: Option<usize> => with_max_column_contribution,
+    explicit_referential_actions: bool => with_explicit_referential_actions,
+    data_type_case: Option<KeywordCase> => with_data_type_case,
+    preserve_comments: bool => with_preserve_comments,
+    align_insert_values: bool => with_align_insert_values,
+    compact: bool => with_compact,
+    trailing_comma_last_item: bool => with_trailing_comma_last_item,
+    global_alignment: bool => with_global_alignment,
+    only_tables: Vec<String> => with_only_tables,
+    hoist_inline_keys: bool => with_hoist_inline_keys,
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// Things which don't stop us formatting, but which a nit-picker would point
+/// out anyway.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A foreign key over a column its `CREATE TABLE` doesn't declare, e.g.
+    /// because of a typo.
+    UnknownForeignKeyColumn {
+        table: String,
+        /// The name of the constraint, if it has one.
+        constraint: Option<String>,
+        column: String,
+    },
+    /// [`FormatOptions::trailing_comma_last_item`](crate::FormatOptions::trailing_comma_last_item)
+    /// was asked for, but either the separator has no trailing comma or the
+    /// dialect wouldn't parse one after the last item, so it was left out.
+    TrailingCommaIgnored { table: String },
+    /// One of [`FormatOptions::only_tables`](crate::FormatOptions::only_tables)
+    /// has no `CREATE TABLE` in the input.
+    UnknownTable { table: String },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::UnknownForeignKeyColumn {
+                table,
+                constraint,
+                column,
+            } => {
+                write!(f, "{}: foreign key ", table)?;
+                if let Some(constraint) = constraint {
+                    write!(f, "{} ", constraint)?;
+                }
+                write!(f, "refers to unknown column {}", column)
+            }
+            Warning::TrailingCommaIgnored { table } => write!(
+                f,
+                "{}: no trailing comma after the last item; it needs the trailing separator and a dialect which allows it",
+                table
+            ),
+            Warning::UnknownTable { table } => write!(f, "{}: no such table to format", table),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Formats every `tests/fixtures/<dialect>/<name>.sql` with the default
+//! options and compares it with the neighbouring `<name>.expected.sql`.
+//!
+//! The expected files hold exactly what the CLI would print, i.e. including
+//! the final newline, and must themselves come out of the formatter
+//! unchanged.
+
+use std::{fs, path::PathBuf};
+
+use ant_farmer::AntFarmer;
+
+/// Every `(dialect, input, expected)` under `tests/fixtures`.
+fn fixtures() -> Vec<(String, PathBuf, PathBuf)> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut fixtures = vec![];
+    for dialect in fs::read_dir(&root).unwrap() {
+        let dialect = dialect.unwrap().path();
+        let name = dialect.file_name().unwrap().to_string_lossy().into_owned();
+
+        for input in fs::read_dir(&dialect).unwrap() {
+            let input = input.unwrap().path();
+            let file_name = input.file_name().unwrap().to_string_lossy();
+            if let Some(stem) = file_name
+                .strip_suffix(".sql")
+                .filter(|stem| !stem.ends_with(".expected"))
+            {
+                let expected = dialect.join(format!("{}.expected.sql", stem));
+                fixtures.push((name.clone(), input, expected));
+            }
+        }
+    }
+    fixtures.sort();
+
+    fixtures
+}
+
+#[test]
+fn test_fixtures() {
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures found");
+
+    let mut failures = vec![];
+    for (dialect, input, expected) in fixtures {
+        let ant_farmer = AntFarmer::try_from_dialect_name(&dialect).unwrap();
+        let sql = fs::read_to_string(&input).unwrap();
+        let expected = fs::read_to_string(&expected)
+            .unwrap_or_else(|err| panic!("{}: {}", expected.display(), err));
+
+        let result = match ant_farmer.format(&sql) {
+            Ok(formatted) => formatted + "\n",
+            Err(err) => {
+                failures.push(format!("{}: {}", input.display(), err));
+                continue;
+            }
+        };
+
+        if result != expected {
+            failures.push(format!(
+                "{}:\n--- expected\n{}--- result\n{}",
+                input.display(),
+                expected,
+                result,
+            ));
+            continue;
+        }
+
+        // Nothing should be lost, or move, on a second pass.
+        match ant_farmer.format(&expected) {
+            Ok(again) if format!("{}\n", again) == expected => {}
+            Ok(again) => failures.push(format!(
+                "{}: not stable when formatted again:\n{}",
+                input.display(),
+                again,
+            )),
+            Err(err) => failures.push(format!(
+                "{}: the output doesn't parse: {}",
+                input.display(),
+                err
+            )),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,2 @@
+CREATE TABLE a (id INT NOT NULL, name NVARCHAR(50) NULL, CONSTRAINT pk_a PRIMARY KEY CLUSTERED (id)) WITH (DATA_COMPRESSION = PAGE, FILLFACTOR = 80);
+CREATE TABLE b (id INT NOT NULL) WITH (CLUSTERED INDEX (id ASC), DISTRIBUTION = HASH(id));
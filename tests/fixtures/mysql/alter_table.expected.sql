@@ -0,0 +1,6 @@
+ALTER TABLE users
+    DROP COLUMN legacy_id
+  , MODIFY COLUMN email VARCHAR(320) NOT NULL DEFAULT '' AFTER name
+  , CHANGE COLUMN nick nickname VARCHAR(64) NULL FIRST
+  , ADD INDEX ix_email (email)
+;
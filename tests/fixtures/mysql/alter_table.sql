@@ -0,0 +1 @@
+alter table users drop column legacy_id, modify column email varchar(320) not null default '' after name, change column nick nickname varchar(64) null first, add index ix_email (email);
@@ -0,0 +1,7 @@
+CREATE TABLE a (
+    id         INT UNSIGNED NOT NULL                                                       AUTO_INCREMENT
+  , name       VARCHAR(64)  NOT NULL DEFAULT ''
+  , updated_at TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP
+  , CONSTRAINT pk_a PRIMARY KEY (id)
+)
+;
@@ -0,0 +1,12 @@
+CREATE TABLE files (
+    id       BINARY(16)     NOT NULL
+  , checksum VARBINARY(255)     NULL
+  , flag     BINARY             NULL
+  , body     BLOB           NOT NULL
+  , original LONGBLOB           NULL
+  , thumb    MEDIUMBLOB         NULL
+  , tiny     TINYBLOB           NULL
+  , bits     BIT(8)             NULL
+  , PRIMARY KEY (id)
+)
+;
@@ -0,0 +1 @@
+CREATE TABLE people (id INT NOT NULL, name VARCHAR(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci NOT NULL, email VARCHAR(320) COLLATE ascii_bin NOT NULL, born DATE NULL, bio TEXT CHARACTER SET latin1 NULL DEFAULT NULL);
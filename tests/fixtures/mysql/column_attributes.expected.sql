@@ -0,0 +1,8 @@
+CREATE TABLE pets (
+    id       INT  NOT NULL AUTO_INCREMENT
+  , owner_id INT  NOT NULL REFERENCES people (id) ON DELETE CASCADE
+  , qty      INT      NULL CHECK (qty > 0)
+  , name     TEXT NOT NULL COMMENT 'the pet''s name'
+  , PRIMARY KEY (id)
+)
+;
@@ -0,0 +1,10 @@
+CREATE TABLE operators_create_consumers (
+    operator_api_key_id    INT(11)  NOT NULL
+  , operator_ip_address_id INT(11)  NOT NULL
+  , create_consumers       JSON         NULL
+  , created_date           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP()
+  , CONSTRAINT fk_operators_create_consumers_operator_api_key_id    FOREIGN KEY (operator_api_key_id)                         REFERENCES api_keys              (id)
+  , CONSTRAINT fk_operators_create_consumers_operator_ip_address_id FOREIGN KEY (operator_ip_address_id)                      REFERENCES operator_ip_addresses (id)
+  , CONSTRAINT uq_operator_api_key_id_operator_ip_address_id        UNIQUE      (operator_api_key_id, operator_ip_address_id)
+)
+;
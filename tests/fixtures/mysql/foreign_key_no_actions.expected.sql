@@ -0,0 +1,8 @@
+CREATE TABLE pets (
+    id       INT NOT NULL
+  , owner_id INT NOT NULL
+  , vet_id   INT     NULL
+  , CONSTRAINT fk_pets_owner_id FOREIGN KEY (owner_id) REFERENCES people (id)
+  , CONSTRAINT fk_pets_vet      FOREIGN KEY (vet_id)   REFERENCES vets   (id)
+)
+;
@@ -0,0 +1 @@
+CREATE TABLE a (id INT NOT NULL, price DECIMAL(10,2) NOT NULL DEFAULT 0, quantity INT NULL, total DECIMAL(10,2) GENERATED ALWAYS AS (price * quantity) STORED NOT NULL);
@@ -0,0 +1,7 @@
+CREATE TABLE settings (
+    id     INT  NOT NULL
+  , config JSON NOT NULL DEFAULT ('{}')
+  , tags   JSON          DEFAULT (JSON_ARRAY())
+  , meta   JSON     NULL DEFAULT ('{"Key": ["Null", null]}')
+)
+;
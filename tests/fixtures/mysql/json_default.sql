@@ -0,0 +1 @@
+CREATE TABLE settings (id INT NOT NULL, config JSON NOT NULL DEFAULT ('{}'), tags json DEFAULT (JSON_ARRAY()), meta JSON NULL DEFAULT ('{"Key": ["Null", null]}'));
@@ -0,0 +1,7 @@
+CREATE TABLE operators_create_consumers (
+    operator_api_key_id    INT(11)  NOT NULL
+  , operator_ip_address_id INT(11)  NOT NULL
+  , create_consumers       JSON         NULL
+  , created_date           DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP()
+)
+;
@@ -0,0 +1,7 @@
+CREATE TABLE a (
+    id   INT  NOT NULL
+  , body TEXT
+  , KEY ix (id)
+  , FULLTEXT KEY ft (body)
+)
+;
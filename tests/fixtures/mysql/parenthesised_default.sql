@@ -0,0 +1 @@
+CREATE TABLE a (id INT NOT NULL DEFAULT 0, due DATE NOT NULL DEFAULT (CURRENT_DATE + INTERVAL 1 DAY), tags JSON NOT NULL DEFAULT (JSON_ARRAY()), note VARCHAR(16) NULL DEFAULT 'none');
@@ -0,0 +1,8 @@
+CREATE TABLE operators_create_consumers (
+    id                  INT(11) NOT NULL
+  , operator_api_key_id INT(11) NOT NULL
+  , CONSTRAINT pk_operators_create_consumers                     PRIMARY KEY (id)                  USING BTREE
+  , CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY (operator_api_key_id)             REFERENCES api_keys (id)
+  , CONSTRAINT uq_operator_api_key_id                            UNIQUE      (operator_api_key_id) USING HASH
+)
+;
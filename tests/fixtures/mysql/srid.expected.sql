@@ -0,0 +1,7 @@
+CREATE TABLE places (
+    id       INT                   NOT NULL
+  , name     VARCHAR(64)               NULL
+  , location POINT       SRID 4326 NOT NULL
+  , boundary GEOMETRY    SRID 0        NULL
+)
+;
@@ -0,0 +1 @@
+CREATE TABLE places (id INT NOT NULL, name VARCHAR(64) NULL, location POINT NOT NULL SRID 4326, boundary GEOMETRY NULL SRID 0);
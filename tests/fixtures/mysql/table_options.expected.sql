@@ -0,0 +1,4 @@
+CREATE TABLE a (
+    id INT NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci AUTO_INCREMENT=5
+;
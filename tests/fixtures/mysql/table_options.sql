@@ -0,0 +1 @@
+create table a (id int not null) collate=utf8mb4_unicode_ci default charset=utf8mb4 auto_increment=5 engine=InnoDB;
@@ -0,0 +1,7 @@
+CREATE TABLE operators_create_consumers (
+    operator_api_key_id    INT(11) NOT NULL
+  , operator_ip_address_id INT(11) NOT NULL
+  , CONSTRAINT fk_operators_create_consumers_operator_api_key_id FOREIGN KEY                                               (operator_api_key_id)                         REFERENCES api_keys (id)
+  ,                                                              UNIQUE KEY  uq_operator_api_key_id_operator_ip_address_id (operator_api_key_id, operator_ip_address_id)
+)
+;
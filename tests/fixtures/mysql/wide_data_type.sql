@@ -0,0 +1 @@
+CREATE TABLE a (id INT NOT NULL, s ENUM('draft','published','archived','deleted') NOT NULL DEFAULT 'draft', n TEXT NULL);
@@ -0,0 +1,6 @@
+CREATE TABLE a (
+    id    INT          NOT NULL DEFAULT 0
+  , label VARCHAR(255)     NULL DEFAULT 'a rather long default value for a label'
+  , n     TEXT         NOT NULL
+)
+;
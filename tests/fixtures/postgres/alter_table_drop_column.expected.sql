@@ -0,0 +1,4 @@
+ALTER TABLE IF EXISTS users
+    DROP COLUMN a
+  , DROP COLUMN IF EXISTS b CASCADE
+;
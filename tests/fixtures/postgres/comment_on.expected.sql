@@ -0,0 +1,14 @@
+CREATE TABLE people (
+    id   INTEGER NOT NULL
+  , name TEXT        NULL
+)
+;
+
+COMMENT ON TABLE people IS 'Everyone we know'
+;
+
+COMMENT ON COLUMN public.people.name IS 'What they''re called'
+;
+
+COMMENT ON COLUMN people.id IS NULL
+;
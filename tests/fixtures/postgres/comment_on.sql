@@ -0,0 +1,4 @@
+CREATE TABLE people (id INTEGER NOT NULL, name TEXT NULL);
+comment on table people is 'Everyone we know';
+COMMENT ON COLUMN public.people.name IS 'What they''re called';
+COMMENT ON COLUMN people.id IS NULL;
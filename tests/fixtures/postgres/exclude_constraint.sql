@@ -0,0 +1 @@
+CREATE TABLE bookings (id INTEGER NOT NULL, room INTEGER NOT NULL, during TSRANGE NOT NULL, PRIMARY KEY (id), CONSTRAINT no_double_booking EXCLUDE USING gist (room WITH =, during WITH &&) WHERE (id > 0), EXCLUDE USING gist (during WITH &&));
@@ -0,0 +1,7 @@
+CREATE TABLE settings (
+    id     INTEGER NOT NULL
+  , config JSONB   NOT NULL DEFAULT '{}'::JSONB
+  , tags   JSONB            DEFAULT '[]'::JSONB
+  , meta   JSON        NULL DEFAULT '{"a": [1, "B"]}'::JSON
+)
+;
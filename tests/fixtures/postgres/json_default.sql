@@ -0,0 +1 @@
+CREATE TABLE settings (id INTEGER NOT NULL, config JSONB NOT NULL DEFAULT '{}'::jsonb, tags jsonb DEFAULT '[]'::JSONB, meta JSON NULL DEFAULT '{"a": [1, "B"]}'::json);
@@ -0,0 +1,5 @@
+CREATE TABLE api_keys (
+    id   INTEGER NOT NULL DEFAULT nextval('api_keys_id_seq'::REGCLASS)
+  , name TEXT    NOT NULL DEFAULT 'unnamed'::TEXT
+)
+;
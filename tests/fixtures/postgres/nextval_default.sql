@@ -0,0 +1 @@
+CREATE TABLE api_keys (id integer DEFAULT nextval('api_keys_id_seq'::regclass) NOT NULL, name text NOT NULL DEFAULT 'unnamed'::text);
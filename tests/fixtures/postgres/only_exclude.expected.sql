@@ -0,0 +1,6 @@
+CREATE TABLE bookings (
+    room   INTEGER NOT NULL
+  , during TSRANGE NOT NULL
+  , EXCLUDE USING gist (room WITH =, during WITH &&)
+)
+;
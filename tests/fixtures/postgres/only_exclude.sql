@@ -0,0 +1 @@
+CREATE TABLE bookings (room INTEGER NOT NULL, during TSRANGE NOT NULL, EXCLUDE USING gist (room WITH =, during WITH &&));